@@ -1,11 +1,12 @@
 use cosmwasm_schema::write_api;
 
-use seibond_contract::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use seibond_contract::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecuteMsg,
         query: QueryMsg,
+        migrate: MigrateMsg,
     }
 }
@@ -1,26 +1,74 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, StdResult, Binary, Uint128, WasmMsg, Addr};
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CosmosMsg, Decimal, Deps,
+    DepsMut, Env, Event, MessageInfo, Order, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
+};
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{BOND_COUNT, BONDS, SEIX_CONTRACT, Bond};
-use cw2::set_contract_version;
-use cw20::Cw20ExecuteMsg;
+use crate::msg::{
+    AccruedInterestResponse, AuctionPriceResponse, AuctionResponse, BondInfo, BondMetadata, BondResponse, BondsResponse, ConfigResponse,
+    DefaultsResponse,
+    ExecuteMsg, InstantiateMsg, MigrateMsg, NextCouponResponse, OfferingResponse, OracleQueryMsg, OrderResponse,
+    OrdersResponse, QueryMsg, RateFixingsResponse, ReceiveMsg, ReferenceRateResponse, RoundingPolicy,
+    SubscriptionResponse,
+};
+use crate::state::{
+    denom_key, AUCTION_COUNT, AUCTIONS, BIDS, BOND_COUNT, BONDS, CONFIG, DEFAULTS, LEGACY_BONDS, LEGACY_SEIX_CONTRACT,
+    OFFERING_COUNT, OFFERINGS, ORDER_COUNT, ORDERS, RATE_FIXINGS, SUBSCRIPTIONS, Auction, AuctionStatus, Bond,
+    BondStatus, BondTerms, CallTerms, Config, DayCount, DefaultPolicy, DefaultRecord, DefaultResolution, FeeSchedule, Offering, OfferingStatus, PriceDecay,
+    Collateral, FixingSource, FloatingRate, PutTerms, RateFixing, RedemptionPrice, SellOrder,
+};
+use cw2::{get_contract_version, set_contract_version};
+use semver::Version;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw721::receiver::Cw721ReceiveMsg;
+use cw721::msg::{NftInfoResponse, OwnerOfResponse, TokensResponse};
+use cw721::{Approval, Expiration};
+use cw_storage_plus::{Bound, Map};
+use cw_utils::{one_coin, PaymentError};
 
 // version info for migration
 const CONTRACT_NAME: &str = "crates.io:seibond-contract";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Pagination limits for listing queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
+// Number of subscriptions or bids settled per call when closing an offering or auction,
+// bounded so that closing stays within the block gas limit
+const DEFAULT_SETTLE_LIMIT: u32 = 30;
+const MAX_SETTLE_LIMIT: u32 = 100;
+
+// Number of seconds in a day, used to pro-rate the annual interest rate
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
 // Instantiate the contract
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
     _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
-    // Store the SeiX token contract address provided during instantiation
-    SEIX_CONTRACT.save(deps.storage, &Addr::unchecked(msg.seix_contract))?;
+) -> Result<Response, ContractError> {
+    // Store the fee configuration, with the SeiX token as the fee token
+    let admin = match msg.admin {
+        Some(admin) => validate_addr(deps.api, "admin", &admin)?,
+        None => info.sender,
+    };
+    let config = Config {
+        admin,
+        treasury: validate_addr(deps.api, "treasury", &msg.treasury)?,
+        fee_token: validate_addr(deps.api, "seix_contract", &msg.seix_contract)?,
+        fees: msg.fees,
+        default_policy: msg.default_policy.unwrap_or_default(),
+        rate_oracle: msg
+            .rate_oracle
+            .map(|oracle| validate_addr(deps.api, "rate_oracle", &oracle))
+            .transpose()?,
+        trusted_tokens: validate_tokens(deps.api, msg.trusted_tokens.unwrap_or_default())?,
+    };
+    CONFIG.save(deps.storage, &config)?;
 
     // Initialize the bond count
     BOND_COUNT.save(deps.storage, &0)?;
@@ -30,7 +78,132 @@ pub fn instantiate(
 
     Ok(Response::new()
         .add_attribute("method", "instantiate")
-        .add_attribute("seix_contract", msg.seix_contract))
+        .add_attribute("seix_contract", msg.seix_contract)
+        .add_attribute("treasury", config.treasury)
+        .add_attribute("admin", config.admin))
+}
+
+// Migrate the contract to this version
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::WrongContract { contract: stored.contract });
+    }
+
+    // Refuse downgrades, migrating to the same version is a no-op
+    let stored_version = parse_version(&stored.version)?;
+    let version = parse_version(CONTRACT_VERSION)?;
+    if stored_version > version {
+        return Err(ContractError::Downgrade {
+            from: stored.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // Before 0.2.0 only the SeiX contract was stored, it becomes the fee token of a config
+    // completed from the message. Bonds were keyed by the string of their ID and stored
+    // without escrow, convert them and rewrite them under u64 keys.
+    let mut migrated_bonds = 0u64;
+    if stored_version < Version::new(0, 2, 0) {
+        let legacy_config = msg.legacy_config.ok_or(ContractError::LegacyConfigRequired {})?;
+        let config = Config {
+            admin: validate_addr(deps.api, "admin", &legacy_config.admin)?,
+            treasury: validate_addr(deps.api, "treasury", &legacy_config.treasury)?,
+            fee_token: LEGACY_SEIX_CONTRACT.load(deps.storage)?,
+            fees: legacy_config.fees,
+            default_policy: DefaultPolicy::default(),
+            rate_oracle: None,
+            trusted_tokens: vec![],
+        };
+        CONFIG.save(deps.storage, &config)?;
+        LEGACY_SEIX_CONTRACT.remove(deps.storage);
+
+        let legacy = LEGACY_BONDS
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        if !legacy.is_empty() {
+            let denom = match msg.legacy_denom {
+                Some(Denom::Cw20(token)) => Denom::Cw20(deps.api.addr_validate(token.as_str())?),
+                Some(denom) => denom,
+                None => return Err(ContractError::LegacyDenomRequired {}),
+            };
+            for (key, legacy_bond) in legacy {
+                let bond_id: u64 = key.parse().map_err(|_| {
+                    StdError::generic_err(format!("Invalid legacy bond key: {}", key))
+                })?;
+                LEGACY_BONDS.remove(deps.storage, key);
+                BONDS.save(deps.storage, bond_id, &legacy_bond.upgrade(denom.clone()))?;
+                migrated_bonds += 1;
+            }
+        }
+    }
+
+    // 0.3.0 tracks and indexes the status of bonds. Settled bonds used to be removed, so
+    // every stored bond is outstanding: pending until its escrow covers the face value and
+    // interest still owed, active after. Saving them again also rewrites rates 0.2.0 stored
+    // in whole percent as a Decimal.
+    let mut reindexed_bonds = 0u64;
+    if stored_version < Version::new(0, 3, 0) {
+        let bonds = BONDS
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (bond_id, mut bond) in bonds {
+            let owed = bond.face_value + unpaid_interest(&bond, &[], bond.maturity);
+            bond.status = if bond.escrow < owed { BondStatus::Pending } else { BondStatus::Active };
+            BONDS.save(deps.storage, bond_id, &bond)?;
+            reindexed_bonds += 1;
+        }
+    }
+
+    // Before 0.4.0 the interest rate of a floating-rate bond was its cap, move the cap into
+    // the floating rate and report the rate of the latest fixing instead
+    let mut floating_bonds = 0u64;
+    if stored_version < Version::new(0, 4, 0) {
+        let bonds = BONDS
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (bond_id, mut bond) in bonds {
+            let Some(floating_rate) = bond.floating_rate.as_mut() else {
+                continue;
+            };
+            floating_rate.cap = bond.interest_rate;
+            if let Some(latest) = load_fixings(deps.storage, bond_id)?.pop() {
+                bond.interest_rate = latest.rate;
+            }
+            BONDS.save(deps.storage, bond_id, &bond)?;
+            floating_bonds += 1;
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION)
+        .add_attribute("migrated_bonds", migrated_bonds.to_string())
+        .add_attribute("reindexed_bonds", reindexed_bonds.to_string())
+        .add_attribute("floating_bonds", floating_bonds.to_string()))
+}
+
+// Validate a user supplied address, naming the message field in the error
+fn validate_addr(api: &dyn Api, field: &str, address: &str) -> Result<Addr, ContractError> {
+    api.addr_validate(address).map_err(|err| ContractError::InvalidAddress {
+        field: field.to_string(),
+        address: address.to_string(),
+        reason: err.to_string(),
+    })
+}
+
+fn validate_tokens(api: &dyn Api, tokens: Vec<String>) -> Result<Vec<Addr>, ContractError> {
+    tokens.iter().map(|token| validate_addr(api, "trusted_tokens", token)).collect()
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    version
+        .parse()
+        .map_err(|_| StdError::generic_err(format!("Invalid contract version: {}", version)))
 }
 
 // Execute contract methods
@@ -42,170 +215,2673 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::MintBond { face_value, interest_rate, maturity } => {
-            mint_bond(deps, env, info, face_value, interest_rate, maturity)
+        ExecuteMsg::MintBond {
+            face_value,
+            interest_rate,
+            maturity,
+            coupon_frequency,
+            day_count,
+            call_terms,
+            put_terms,
+            collateral,
+            floating_rate,
+        } => {
+            // The principal is escrowed from the native coins sent with the message
+            let coin = principal_coin(&info, collateral.as_ref())?;
+            let funds = (Denom::Native(coin.denom), coin.amount);
+            let day_count = day_count.unwrap_or_default();
+            let terms = BondTerms {
+                face_value,
+                interest_rate,
+                maturity,
+                coupon_frequency,
+                day_count,
+                call_terms,
+                put_terms,
+                collateral,
+                floating_rate,
+            };
+            mint_bond(deps, env, info.sender, funds, FeePayment::Allowance, terms)
         }
         ExecuteMsg::TransferBond { bond_id, new_holder } => {
-            transfer_bond(deps, info, bond_id, new_holder)
+            let new_holder = validate_addr(deps.api, "new_holder", &new_holder)?;
+            transfer_bond(deps, env, info.sender, FeePayment::Allowance, bond_id, new_holder)
+        }
+        ExecuteMsg::TransferNft { recipient, token_id } => {
+            let recipient = validate_addr(deps.api, "recipient", &recipient)?;
+            let bond_id = parse_token_id(&token_id)?;
+            transfer_bond(deps, env, info.sender, FeePayment::Allowance, bond_id, recipient)
+        }
+        ExecuteMsg::SendNft { contract, token_id, msg } => {
+            send_nft(deps, env, info, contract, token_id, msg)
+        }
+        ExecuteMsg::Approve { spender, token_id, expires } => {
+            approve(deps, env, info, spender, parse_token_id(&token_id)?, expires)
+        }
+        ExecuteMsg::Revoke { spender, token_id } => {
+            revoke(deps, info, spender, parse_token_id(&token_id)?)
         }
         ExecuteMsg::RedeemBond { bond_id } => {
-            redeem_bond(deps, env, info, bond_id)
+            redeem_bond(deps, env, info.sender, FeePayment::Allowance, bond_id)
+        }
+        ExecuteMsg::ClaimCoupon { bond_id } => claim_coupon(deps, env, bond_id),
+        ExecuteMsg::OpenOffering {
+            denom,
+            price,
+            interest_rate,
+            maturity,
+            coupon_frequency,
+            day_count,
+            start,
+            end,
+            min_raise,
+            max_raise,
+        } => {
+            if let Denom::Cw20(token) = &denom {
+                validate_addr(deps.api, "denom", token.as_str())?;
+            }
+            let offering = Offering {
+                issuer: info.sender,
+                denom,
+                price,
+                interest_rate,
+                maturity,
+                coupon_frequency,
+                day_count: day_count.unwrap_or_default(),
+                start: start.unwrap_or_else(|| env.block.time.seconds()),
+                end,
+                min_raise,
+                max_raise,
+                subscribed: Uint128::zero(),
+                raised: Uint128::zero(),
+                status: OfferingStatus::Open,
+                closed_through: None,
+                closed_at: None,
+            };
+            open_offering(deps, env, offering)
+        }
+        ExecuteMsg::Subscribe { offering_id } => {
+            let coin = one_coin(&info)?;
+            let funds = (Denom::Native(coin.denom), coin.amount);
+            subscribe(deps, env, info.sender, offering_id, funds)
+        }
+        ExecuteMsg::CloseOffering { offering_id, limit } => close_offering(deps, env, offering_id, limit),
+        ExecuteMsg::FundBond { bond_id } => {
+            let coin = one_coin(&info)?;
+            fund_bond(deps, env, info.sender, bond_id, (Denom::Native(coin.denom), coin.amount))
+        }
+        ExecuteMsg::OpenAuction {
+            denom,
+            face_value,
+            interest_rate,
+            maturity,
+            coupon_frequency,
+            day_count,
+            supply,
+            start_price,
+            floor_price,
+            decay,
+            start,
+            end,
+        } => {
+            if let Denom::Cw20(token) = &denom {
+                validate_addr(deps.api, "denom", token.as_str())?;
+            }
+            let day_count = day_count.unwrap_or_default();
+            let auction = Auction {
+                issuer: info.sender,
+                denom,
+                terms: BondTerms {
+                    face_value,
+                    interest_rate,
+                    maturity,
+                    coupon_frequency,
+                    day_count,
+                    call_terms: None,
+                    put_terms: None,
+                    collateral: None,
+                    floating_rate: None,
+                },
+                supply,
+                start_price,
+                floor_price,
+                decay,
+                start: start.unwrap_or_else(|| env.block.time.seconds()),
+                end,
+                committed: Uint128::zero(),
+                clearing_price: None,
+                status: AuctionStatus::Open,
+                closed_through: None,
+                closed_at: None,
+            };
+            open_auction(deps, env, auction)
+        }
+        ExecuteMsg::Bid { auction_id } => {
+            let coin = one_coin(&info)?;
+            bid(deps, env, info.sender, auction_id, (Denom::Native(coin.denom), coin.amount))
+        }
+        ExecuteMsg::CloseAuction { auction_id, limit } => close_auction(deps, env, auction_id, limit),
+        ExecuteMsg::ListBond { bond_id, denom, price, expires } => {
+            if let Denom::Cw20(token) = &denom {
+                validate_addr(deps.api, "denom", token.as_str())?;
+            }
+            list_bond(deps, env, info.sender, bond_id, denom, price, expires)
+        }
+        ExecuteMsg::BuyBond { order_id } => {
+            let coin = one_coin(&info)?;
+            let payment = (Denom::Native(coin.denom), coin.amount);
+            buy_bond(deps, env, info.sender, order_id, payment, FeePayment::Allowance)
+        }
+        ExecuteMsg::CancelOrder { order_id } => cancel_order(deps, info, order_id),
+        ExecuteMsg::NoticeCall { bond_id } => notice_call(deps, env, info.sender, bond_id),
+        ExecuteMsg::CallBond { bond_id } => {
+            // Native coins attached top up the escrow for a call price above it
+            let funds = if info.funds.is_empty() {
+                None
+            } else {
+                let coin = one_coin(&info)?;
+                Some((Denom::Native(coin.denom), coin.amount))
+            };
+            call_bond(deps, env, info.sender, bond_id, funds)
+        }
+        ExecuteMsg::PutBond { bond_id, amount } => {
+            put_bond(deps, env, info.sender, FeePayment::Allowance, bond_id, amount)
+        }
+        ExecuteMsg::TopUpCollateral { bond_id } => {
+            let coin = one_coin(&info)?;
+            top_up_collateral(deps, info.sender, bond_id, (Denom::Native(coin.denom), coin.amount))
+        }
+        ExecuteMsg::WithdrawExcessCollateral { bond_id } => {
+            withdraw_excess_collateral(deps, info.sender, bond_id)
         }
+        ExecuteMsg::CancelBond { bond_id } => cancel_bond(deps, info.sender, bond_id),
+        ExecuteMsg::ClaimCollateral { bond_id } => claim_collateral(deps, env, info.sender, bond_id),
+        ExecuteMsg::MarkDefault { bond_id } => mark_default(deps, env, bond_id),
+        ExecuteMsg::CureDefault { bond_id } => {
+            let coin = one_coin(&info)?;
+            cure_default(deps, env, info.sender, bond_id, (Denom::Native(coin.denom), coin.amount))
+        }
+        ExecuteMsg::FixRate { bond_id } => fix_rate(deps, env, bond_id),
+        ExecuteMsg::UpdateConfig {
+            admin,
+            treasury,
+            fee_token,
+            fees,
+            default_policy,
+            rate_oracle,
+            trusted_tokens,
+        } => {
+            let update = ConfigUpdate { admin, treasury, fee_token, fees, default_policy, rate_oracle, trusted_tokens };
+            update_config(deps, info, update)
+        }
+        ExecuteMsg::Receive(wrapper) => receive_cw20(deps, env, info, wrapper),
     }
 }
 
-// Mint a new bond
-pub fn mint_bond(
+// Changes to the platform configuration, fields left empty are kept as they are
+pub struct ConfigUpdate {
+    pub admin: Option<String>,
+    pub treasury: Option<String>,
+    pub fee_token: Option<String>,
+    pub fees: Option<FeeSchedule>,
+    pub default_policy: Option<DefaultPolicy>,
+    pub rate_oracle: Option<String>,
+    pub trusted_tokens: Option<Vec<String>>,
+}
+
+// Update the platform configuration
+pub fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    let ConfigUpdate { admin, treasury, fee_token, fees, default_policy, rate_oracle, trusted_tokens } = update;
+    let mut config = CONFIG.load(deps.storage)?;
+
+    // Only the admin can change the configuration
+    if config.admin != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(admin) = admin {
+        config.admin = validate_addr(deps.api, "admin", &admin)?;
+    }
+    if let Some(treasury) = treasury {
+        config.treasury = validate_addr(deps.api, "treasury", &treasury)?;
+    }
+    if let Some(fee_token) = fee_token {
+        config.fee_token = validate_addr(deps.api, "fee_token", &fee_token)?;
+    }
+    if let Some(fees) = fees {
+        config.fees = fees;
+    }
+    if let Some(default_policy) = default_policy {
+        config.default_policy = default_policy;
+    }
+    if let Some(rate_oracle) = rate_oracle {
+        config.rate_oracle = Some(validate_addr(deps.api, "rate_oracle", &rate_oracle)?);
+    }
+    if let Some(trusted_tokens) = trusted_tokens {
+        config.trusted_tokens = validate_tokens(deps.api, trusted_tokens)?;
+    }
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("method", "update_config"))
+}
+
+// How the platform fee for an operation reaches the contract
+pub enum FeePayment {
+    // SeiX sent along with the operation through the Receive hook
+    Sent(Uint128),
+    // SeiX pulled from the caller's allowance with TransferFrom
+    Allowance,
+}
+
+// Handle CW20 tokens sent to the contract through the Receive hook
+pub fn receive_cw20(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    face_value: Uint128,
-    interest_rate: u64,
-    maturity: u64,
-) -> StdResult<Response> {
-    let bond_count = BOND_COUNT.load(deps.storage)?;
-    let new_bond_id = bond_count + 1;
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    // The calling contract is the CW20 token, the original sender is in the wrapper
+    let sender = validate_addr(deps.api, "sender", &wrapper.sender)?;
+    let config = CONFIG.load(deps.storage)?;
+    let from_fee_token = info.sender == config.fee_token;
+
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::MintBond {
+            face_value,
+            interest_rate,
+            maturity,
+            coupon_frequency,
+            day_count,
+            call_terms,
+            put_terms,
+            collateral,
+            floating_rate,
+        } => {
+            // Native coins cannot be attached to a CW20 Send
+            if let Some(Collateral { denom: Denom::Native(_), .. }) = collateral {
+                return Err(ContractError::InvalidCollateral {
+                    reason: "Collateral for a bond funded in CW20 tokens must be a CW20 token".to_string(),
+                });
+            }
+            // CW20 collateral is pulled from the sender's allowance, only a trusted token may
+            // name the issuer it is pulled from
+            if collateral.is_some() {
+                ensure_trusted_token(&config, &info.sender)?;
+            }
+            // SeiX sent with a mint covers the fee first, the rest is the principal
+            let (fee, principal) = if from_fee_token {
+                deduct_sent_fee(wrapper.amount, config.fees.mint)?
+            } else {
+                ensure_trusted_token(&config, &info.sender)?;
+                (FeePayment::Allowance, wrapper.amount)
+            };
+            let funds = (Denom::Cw20(info.sender), principal);
+            let day_count = day_count.unwrap_or_default();
+            let terms = BondTerms {
+                face_value,
+                interest_rate,
+                maturity,
+                coupon_frequency,
+                day_count,
+                call_terms,
+                put_terms,
+                collateral,
+                floating_rate,
+            };
+            mint_bond(deps, env, sender, funds, fee, terms)
+        }
+        ReceiveMsg::TransferBond { bond_id, new_holder } => {
+            let new_holder = validate_addr(deps.api, "new_holder", &new_holder)?;
+            ensure_fee_token(&config, &info.sender)?;
+            transfer_bond(deps, env, sender, FeePayment::Sent(wrapper.amount), bond_id, new_holder)
+        }
+        ReceiveMsg::RedeemBond { bond_id } => {
+            ensure_fee_token(&config, &info.sender)?;
+            redeem_bond(deps, env, sender, FeePayment::Sent(wrapper.amount), bond_id)
+        }
+        ReceiveMsg::Subscribe { offering_id } => {
+            let funds = (Denom::Cw20(info.sender), wrapper.amount);
+            subscribe(deps, env, sender, offering_id, funds)
+        }
+        ReceiveMsg::FundBond { bond_id } => {
+            fund_bond(deps, env, sender, bond_id, (Denom::Cw20(info.sender), wrapper.amount))
+        }
+        ReceiveMsg::Bid { auction_id } => {
+            bid(deps, env, sender, auction_id, (Denom::Cw20(info.sender), wrapper.amount))
+        }
+        ReceiveMsg::CallBond { bond_id } => {
+            let funds = (Denom::Cw20(info.sender), wrapper.amount);
+            call_bond(deps, env, sender, bond_id, Some(funds))
+        }
+        ReceiveMsg::PutBond { bond_id, amount } => {
+            ensure_fee_token(&config, &info.sender)?;
+            put_bond(deps, env, sender, FeePayment::Sent(wrapper.amount), bond_id, amount)
+        }
+        ReceiveMsg::TopUpCollateral { bond_id } => {
+            top_up_collateral(deps, sender, bond_id, (Denom::Cw20(info.sender), wrapper.amount))
+        }
+        ReceiveMsg::CureDefault { bond_id } => {
+            cure_default(deps, env, sender, bond_id, (Denom::Cw20(info.sender), wrapper.amount))
+        }
+        ReceiveMsg::BuyBond { order_id } => {
+            // SeiX sent for an order in SeiX covers the fee first, the rest pays the seller
+            let (fee, price) = if from_fee_token {
+                deduct_sent_fee(wrapper.amount, config.fees.transfer)?
+            } else {
+                ensure_trusted_token(&config, &info.sender)?;
+                (FeePayment::Allowance, wrapper.amount)
+            };
+            buy_bond(deps, env, sender, order_id, (Denom::Cw20(info.sender), price), fee)
+        }
+    }
+}
 
-    // Load the SeiX contract address from state
-    let seix_contract = SEIX_CONTRACT.load(deps.storage)?;
+// Split SeiX sent along with an operation into its platform fee and the remainder
+fn deduct_sent_fee(amount: Uint128, fee: Uint128) -> Result<(FeePayment, Uint128), ContractError> {
+    let remainder = amount.checked_sub(fee).map_err(|_| ContractError::InsufficientFunds {
+        needed: fee,
+        available: amount,
+    })?;
+    Ok((FeePayment::Sent(fee), remainder))
+}
 
-    // Collect platform fee in SeiX token using CW20 transfer
-    let platform_fee = Uint128::new(10);  // Example fee amount
+// Mint a new bond, escrowing the principal and interest sent by the issuer
+pub fn mint_bond(
+    mut deps: DepsMut,
+    env: Env,
+    issuer: Addr,
+    funds: (Denom, Uint128),
+    fee: FeePayment,
+    terms: BondTerms,
+) -> Result<Response, ContractError> {
+    let (denom, amount) = funds;
 
-    let transfer_msg = Cw20ExecuteMsg::Transfer {
-        recipient: "platform_treasury_address".to_string(),
-        amount: platform_fee,
-    };
+    // Coupons must be paid at a positive interval
+    if terms.coupon_frequency == Some(0) {
+        return Err(ContractError::InvalidCouponFrequency {});
+    }
+    if let Some(call_terms) = &terms.call_terms {
+        validate_call_terms(call_terms, terms.maturity)?;
+    }
+    if let Some(put_terms) = &terms.put_terms {
+        validate_put_terms(put_terms, terms.maturity)?;
+    }
+    if let Some(collateral) = &terms.collateral {
+        if let Denom::Cw20(token) = &collateral.denom {
+            validate_addr(deps.api, "collateral", token.as_str())?;
+        }
+        validate_collateral(collateral, terms.face_value)?;
+    }
+    if let Some(floating_rate) = &terms.floating_rate {
+        validate_floating_rate(floating_rate, &terms)?;
+        // The first period is fixed at issuance, from the oracle rather than at the cap
+        query_reference_rate(deps.as_ref())?;
+    }
 
-    // Encode the CW20 transfer message to be sent
-    let wasm_msg = WasmMsg::Execute {
-        contract_addr: seix_contract.to_string(),
-        msg: to_binary(&transfer_msg)?,
-        funds: vec![],
+    let issued_at = env.block.time.seconds();
+    let mut bond = Bond {
+        issuer: issuer.clone(),
+        face_value: terms.face_value,
+        interest_rate: terms.interest_rate,
+        maturity: terms.maturity,
+        holder: issuer.clone(),
+        issued_at,
+        denom,
+        escrow: amount,
+        coupon_frequency: terms.coupon_frequency,
+        coupons_paid: 0,
+        approvals: vec![],
+        day_count: terms.day_count,
+        call_terms: terms.call_terms,
+        call_date: None,
+        put_terms: terms.put_terms,
+        collateral: terms.collateral,
+        status: BondStatus::Active,
+        floating_rate: terms.floating_rate,
     };
 
-    let bond = Bond {
-        issuer: info.sender.clone(),
-        face_value,
-        interest_rate,
-        maturity,
-        holder: info.sender.clone(),
-        issued_at: env.block.time.seconds(),
+    // The issuer must fund the full face value plus the interest owed at maturity,
+    // at the cap for a floating-rate bond
+    let required = bond.face_value + accrued_interest(&bond, &[], bond.maturity);
+    // A collateralized bond may be escrowed in part and repaid later with FundBond
+    if bond.collateral.is_some() {
+        if amount > required {
+            return Err(ContractError::Overfunded { outstanding: required, received: amount });
+        }
+    } else if amount != required {
+        return Err(ContractError::InvalidFunding { required, received: amount });
+    }
+    if amount < required {
+        bond.status = BondStatus::Pending;
+    }
+
+    let bond_count = BOND_COUNT.load(deps.storage)?;
+    let new_bond_id = bond_count + 1;
+
+    // Collect the platform fee in SeiX token from the issuer
+    let config = CONFIG.load(deps.storage)?;
+    let fee_msgs = collect_fee(&config, &issuer, config.fees.mint, fee)?;
+
+    // CW20 collateral is pulled from the issuer, native collateral was attached to the message
+    let collateral_msg = match &bond.collateral {
+        Some(Collateral { denom: Denom::Cw20(token), amount, .. }) => Some(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: issuer.to_string(),
+                recipient: env.contract.address.to_string(),
+                amount: *amount,
+            })?,
+            funds: vec![],
+        }),
+        _ => None,
     };
 
     // Save the new bond in storage
-    BONDS.save(deps.storage, &new_bond_id.to_string(), &bond)?;
+    BONDS.save(deps.storage, new_bond_id, &bond)?;
 
     // Increment bond count
     BOND_COUNT.save(deps.storage, &new_bond_id)?;
 
+    // The rate of the first coupon period of a floating-rate bond is fixed at issuance
+    let (_, fixing_events) = fix_rates(deps.branch(), new_bond_id, &mut bond, issued_at)?;
+
     Ok(Response::new()
-        .add_message(wasm_msg)  // Send the CW20 transfer message
+        .add_messages(fee_msgs)  // Forward the platform fee to the treasury
+        .add_messages(collateral_msg)  // Lock the CW20 collateral
+        .add_events(fixing_events)
         .add_attribute("method", "mint_bond")
-        .add_attribute("bond_id", new_bond_id.to_string()))
+        .add_attribute("bond_id", new_bond_id.to_string())
+        .add_attribute("escrow", amount.to_string()))
 }
 
 // Transfer an existing bond to a new holder
 pub fn transfer_bond(
     deps: DepsMut,
-    info: MessageInfo,
-    bond_id: String,
+    env: Env,
+    sender: Addr,
+    fee: FeePayment,
+    bond_id: u64,
     new_holder: Addr,
-) -> StdResult<Response> {
-    let mut bond = BONDS.load(deps.storage, &bond_id)?;
+) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
 
-    // Ensure that the sender is the current bond holder
-    if bond.holder != info.sender {
-        return Err(StdError::generic_err("Unauthorized: Only the bond holder can transfer the bond"));
+    // Ensure that the sender is the current bond holder or an approved spender
+    if !can_transfer(&env.block, &bond, &sender) {
+        return Err(ContractError::NotHolder { action: "transfer".to_string() });
     }
 
-    // Load the SeiX contract address from state
-    let seix_contract = SEIX_CONTRACT.load(deps.storage)?;
+    // Collect the platform fee in SeiX token from the holder
+    let config = CONFIG.load(deps.storage)?;
+    let fee_msgs = collect_fee(&config, &sender, config.fees.transfer, fee)?;
 
-    // Collect platform fee in SeiX token using CW20 transfer
-    let platform_fee = Uint128::new(5);  // Example fee amount
+    // Update the bond holder, approvals and open orders do not carry over to the new holder
+    bond.holder = new_holder;
+    bond.approvals = vec![];
+    BONDS.save(deps.storage, bond_id, &bond)?;
+    cancel_bond_orders(deps.storage, bond_id)?;
 
-    let transfer_msg = Cw20ExecuteMsg::Transfer {
-        recipient: "platform_treasury_address".to_string(),
-        amount: platform_fee,
-    };
+    Ok(Response::new()
+        .add_messages(fee_msgs)  // Forward the platform fee to the treasury
+        .add_attribute("method", "transfer_bond")
+        .add_attribute("bond_id", bond_id.to_string()))
+}
+
+// Transfer a bond to a contract and notify it through the CW721 ReceiveNft hook
+pub fn send_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let contract = validate_addr(deps.api, "contract", &contract)?;
+    let res = transfer_bond(
+        deps,
+        env,
+        info.sender.clone(),
+        FeePayment::Allowance,
+        parse_token_id(&token_id)?,
+        contract.clone(),
+    )?;
 
-    // Encode the CW20 transfer message to be sent
-    let wasm_msg = WasmMsg::Execute {
-        contract_addr: seix_contract.to_string(),
-        msg: to_binary(&transfer_msg)?,
-        funds: vec![],
+    let receive_msg = Cw721ReceiveMsg {
+        sender: info.sender.to_string(),
+        token_id,
+        msg,
     };
 
-    // Update the bond holder
-    bond.holder = new_holder;
-    BONDS.save(deps.storage, &bond_id, &bond)?;
+    Ok(res
+        .add_message(receive_msg.into_cosmos_msg(contract.clone())?)
+        .add_attribute("receiver", contract))
+}
+
+// Allow a spender to transfer a bond on behalf of its holder
+pub fn approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    bond_id: u64,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+
+    // Only the holder can grant approvals
+    if bond.holder != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let spender = validate_addr(deps.api, "spender", &spender)?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::ExpiredApproval {});
+    }
+
+    // Replace any existing approval for the same spender
+    bond.approvals.retain(|approval| approval.spender != spender);
+    bond.approvals.push(Approval { spender: spender.clone(), expires });
+    BONDS.save(deps.storage, bond_id, &bond)?;
 
     Ok(Response::new()
-        .add_message(wasm_msg)  // Send the CW20 transfer message
-        .add_attribute("method", "transfer_bond")
-        .add_attribute("bond_id", bond_id))
+        .add_attribute("method", "approve")
+        .add_attribute("token_id", bond_id.to_string())
+        .add_attribute("spender", spender))
+}
+
+// Remove the approval of a spender on a bond
+pub fn revoke(
+    deps: DepsMut,
+    info: MessageInfo,
+    spender: String,
+    bond_id: u64,
+) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+
+    // Only the holder can revoke approvals
+    if bond.holder != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let spender = validate_addr(deps.api, "spender", &spender)?;
+    bond.approvals.retain(|approval| approval.spender != spender);
+    BONDS.save(deps.storage, bond_id, &bond)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "revoke")
+        .add_attribute("token_id", bond_id.to_string())
+        .add_attribute("spender", spender))
+}
+
+// CW721 token ids are the decimal string of the bond ID
+fn parse_token_id(token_id: &str) -> Result<u64, ContractError> {
+    token_id.parse().map_err(|_| ContractError::InvalidTokenId {
+        token_id: token_id.to_string(),
+    })
+}
+
+// Load a bond, settled or not
+fn load_bond(deps: Deps, bond_id: u64) -> Result<Bond, ContractError> {
+    BONDS
+        .may_load(deps.storage, bond_id)?
+        .ok_or(ContractError::BondNotFound { bond_id })
+}
+
+// Load a bond for an operation on it, which must still be outstanding
+fn load_outstanding_bond(deps: Deps, bond_id: u64) -> Result<Bond, ContractError> {
+    let bond = load_bond(deps, bond_id)?;
+    if !bond.status.is_outstanding() {
+        return Err(ContractError::BondClosed { bond_id, status: bond.status });
+    }
+    Ok(bond)
+}
+
+// Move a bond to a new status, rejecting transitions its lifecycle does not allow
+fn set_status(bond: &mut Bond, status: BondStatus) -> Result<(), ContractError> {
+    if !bond.status.can_become(status) {
+        return Err(ContractError::InvalidTransition { from: bond.status, to: status });
+    }
+    bond.status = status;
+    Ok(())
+}
+
+// Settle a bond, keeping it on record with the status it ended in
+fn close_bond(
+    storage: &mut dyn Storage,
+    bond_id: u64,
+    bond: &mut Bond,
+    status: BondStatus,
+) -> Result<(), ContractError> {
+    set_status(bond, status)?;
+    settle_bond(storage, bond_id, bond)
+}
+
+// Keep a bond on record as settled: its escrow and collateral have been paid out and its
+// approvals and open orders lapse
+fn settle_bond(storage: &mut dyn Storage, bond_id: u64, bond: &mut Bond) -> Result<(), ContractError> {
+    bond.escrow = Uint128::zero();
+    if let Some(collateral) = bond.collateral.as_mut() {
+        collateral.amount = Uint128::zero();
+    }
+    bond.approvals.clear();
+    BONDS.save(storage, bond_id, bond)?;
+    cancel_bond_orders(storage, bond_id)?;
+    Ok(())
+}
+
+// Load an offering for an operation on it
+fn load_offering(deps: Deps, offering_id: u64) -> Result<Offering, ContractError> {
+    OFFERINGS
+        .may_load(deps.storage, offering_id)?
+        .ok_or(ContractError::OfferingNotFound { offering_id })
+}
+
+// Load an auction for an operation on it
+fn load_auction(deps: Deps, auction_id: u64) -> Result<Auction, ContractError> {
+    AUCTIONS
+        .may_load(deps.storage, auction_id)?
+        .ok_or(ContractError::AuctionNotFound { auction_id })
+}
+
+// Load an order for an operation on it
+fn load_order(deps: Deps, order_id: u64) -> Result<SellOrder, ContractError> {
+    ORDERS
+        .may_load(deps.storage, order_id)?
+        .ok_or(ContractError::OrderNotFound { order_id })
+}
+
+// Check whether an address may transfer the bond: its holder or an unexpired approval
+fn can_transfer(block: &BlockInfo, bond: &Bond, sender: &Addr) -> bool {
+    bond.holder == *sender
+        || bond
+            .approvals
+            .iter()
+            .any(|approval| approval.spender == *sender && !approval.is_expired(block))
 }
 
 // Redeem a bond
 pub fn redeem_bond(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
-    info: MessageInfo,
-    bond_id: String,
-) -> StdResult<Response> {
-    let bond = BONDS.load(deps.storage, &bond_id)?;
+    sender: Addr,
+    fee: FeePayment,
+    bond_id: u64,
+) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
 
     // Ensure that the sender is the bond holder
-    if bond.holder != info.sender {
-        return Err(StdError::generic_err("Unauthorized: Only the bond holder can redeem the bond"));
+    if bond.holder != sender {
+        return Err(ContractError::NotHolder { action: "redeem".to_string() });
     }
 
     // Ensure that the bond has matured
     let current_time = env.block.time.seconds();
     if current_time < bond.issued_at + bond.maturity {
-        return Err(StdError::generic_err("Bond has not matured yet"));
+        return Err(ContractError::NotMatured { matures_at: bond.issued_at + bond.maturity });
+    }
+
+    // Collect the platform fee in SeiX token from the holder
+    let config = CONFIG.load(deps.storage)?;
+    let fee_msgs = collect_fee(&config, &sender, config.fees.redeem, fee)?;
+
+    // Pay the face value plus the interest not yet paid as coupons out of the escrow
+    let (fixings, fixing_events) = fix_rates(deps.branch(), bond_id, &mut bond, current_time)?;
+    let payout = bond.face_value + unpaid_interest(&bond, &fixings, bond.maturity);
+    if payout > bond.escrow {
+        return Err(ContractError::InsufficientFunds { needed: payout, available: bond.escrow });
     }
+    let mut msgs = vec![send_funds(&bond.denom, &bond.holder, payout)?];
+    // A floating-rate bond was escrowed at its cap, the interest it did not accrue is left over
+    let released = bond.escrow - payout;
+    if !released.is_zero() {
+        msgs.push(send_funds(&bond.denom, &bond.issuer, released)?);
+    }
+    msgs.extend(release_collateral(&bond)?);
+    close_bond(deps.storage, bond_id, &mut bond, BondStatus::Redeemed)?;
 
-    // Load the SeiX contract address from state
-    let seix_contract = SEIX_CONTRACT.load(deps.storage)?;
+    Ok(Response::new()
+        .add_messages(fee_msgs)  // Forward the platform fee to the treasury
+        .add_messages(msgs)  // Pay the holder from escrow, return the rest and the collateral to the issuer
+        .add_events(fixing_events)
+        .add_attribute("method", "redeem_bond")
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("payout", payout.to_string()))
+}
 
-    // Collect platform fee in SeiX token using CW20 transfer
-    let platform_fee = Uint128::new(15);  // Example fee amount
+// Pay every coupon that has fallen due to the current holder
+pub fn claim_coupon(
+    mut deps: DepsMut,
+    env: Env,
+    bond_id: u64,
+) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
 
-    let transfer_msg = Cw20ExecuteMsg::Transfer {
-        recipient: "platform_treasury_address".to_string(),
-        amount: platform_fee,
-    };
+    // Each coupon period is paid exactly once
+    let now = env.block.time.seconds();
+    let due = coupons_due(&bond, now);
+    if due <= bond.coupons_paid {
+        return Err(ContractError::NoCouponDue {});
+    }
+    let (fixings, fixing_events) = fix_rates(deps.branch(), bond_id, &mut bond, now)?;
+    let amount = coupon_amount(&bond, &fixings, due);
 
-    // Encode the CW20 transfer message to be sent
-    let wasm_msg = WasmMsg::Execute {
-        contract_addr: seix_contract.to_string(),
-        msg: to_binary(&transfer_msg)?,
-        funds: vec![],
-    };
+    bond.escrow = bond.escrow.checked_sub(amount).map_err(|_| {
+        ContractError::InsufficientFunds { needed: amount, available: bond.escrow }
+    })?;
+    let periods_paid = due - bond.coupons_paid;
+    bond.coupons_paid = due;
+    BONDS.save(deps.storage, bond_id, &bond)?;
 
-    // Optionally, transfer face value + interest to bond holder (in stablecoin)
-    BONDS.remove(deps.storage, &bond_id);
+    let payout_msg = send_funds(&bond.denom, &bond.holder, amount)?;
 
     Ok(Response::new()
-        .add_message(wasm_msg)  // Send the CW20 transfer message
-        .add_attribute("method", "redeem_bond")
-        .add_attribute("bond_id", bond_id))
+        .add_message(payout_msg)  // Pay the coupons to the holder from escrow
+        .add_events(fixing_events)
+        .add_attribute("method", "claim_coupon")
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("holder", bond.holder)
+        .add_attribute("periods_paid", periods_paid.to_string())
+        .add_attribute("amount", amount.to_string()))
 }
 
-// Query contract state
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::GetBond { bond_id } => {
-            let bond = BONDS.load(deps.storage, &bond_id)?;
-            to_binary(&bond)
+// Add funds to the escrow repaying a bond, up to the face value and interest still owed
+pub fn fund_bond(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    bond_id: u64,
+    funds: (Denom, Uint128),
+) -> Result<Response, ContractError> {
+    let (denom, amount) = funds;
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+    if denom != bond.denom {
+        return Err(ContractError::WrongDenom {});
+    }
+
+    // Once the grace period is over a bond still owed on is repaid with CureDefault
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let ended_at = bond.issued_at + bond.maturity + config.default_policy.grace_period;
+    if now > ended_at {
+        return Err(ContractError::GracePeriodOver { ended_at });
+    }
+
+    // Anything above the outstanding obligation would be stuck in escrow after redemption
+    let (fixings, fixing_events) = fix_rates(deps.branch(), bond_id, &mut bond, now)?;
+    let owed = bond.face_value + unpaid_interest(&bond, &fixings, bond.maturity);
+    let outstanding = owed.saturating_sub(bond.escrow);
+    if amount > outstanding {
+        return Err(ContractError::Overfunded { outstanding, received: amount });
+    }
+    bond.escrow += amount;
+    if bond.status == BondStatus::Pending && bond.escrow == owed {
+        set_status(&mut bond, BondStatus::Active)?;
+    }
+    BONDS.save(deps.storage, bond_id, &bond)?;
+
+    Ok(Response::new()
+        .add_events(fixing_events)
+        .add_attribute("method", "fund_bond")
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("funder", sender)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("escrow", bond.escrow.to_string()))
+}
+
+// Open a primary issuance of bonds, charging the issuer the mint fee
+pub fn open_offering(
+    deps: DepsMut,
+    env: Env,
+    offering: Offering,
+) -> Result<Response, ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidOffering { reason: reason.to_string() };
+    if offering.price.is_zero() {
+        return Err(invalid("Price must be positive"));
+    }
+    if offering.max_raise.is_zero() || offering.min_raise > offering.max_raise {
+        return Err(invalid("Minimum raise must not exceed a positive maximum raise"));
+    }
+    if offering.end <= offering.start.max(env.block.time.seconds()) {
+        return Err(invalid("Subscription period must end in the future and after it starts"));
+    }
+    if offering.coupon_frequency == Some(0) {
+        return Err(ContractError::InvalidCouponFrequency {});
+    }
+
+    let offering_id = OFFERING_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+
+    // Collect the platform fee in SeiX token from the issuer
+    let config = CONFIG.load(deps.storage)?;
+    let fee_msgs = collect_fee(&config, &offering.issuer, config.fees.mint, FeePayment::Allowance)?;
+
+    OFFERINGS.save(deps.storage, offering_id, &offering)?;
+    OFFERING_COUNT.save(deps.storage, &offering_id)?;
+
+    Ok(Response::new()
+        .add_messages(fee_msgs)  // Forward the platform fee to the treasury
+        .add_attribute("method", "open_offering")
+        .add_attribute("offering_id", offering_id.to_string())
+        .add_attribute("issuer", offering.issuer))
+}
+
+// Commit funds to an offering during its subscription period
+pub fn subscribe(
+    deps: DepsMut,
+    env: Env,
+    investor: Addr,
+    offering_id: u64,
+    funds: (Denom, Uint128),
+) -> Result<Response, ContractError> {
+    let (denom, amount) = funds;
+    let mut offering = load_offering(deps.as_ref(), offering_id)?;
+    if offering.status != OfferingStatus::Open {
+        return Err(ContractError::OfferingNotOpen { offering_id });
+    }
+    let now = env.block.time.seconds();
+    if now < offering.start || now >= offering.end {
+        return Err(ContractError::OutsideSubscriptionPeriod {
+            start: offering.start,
+            end: offering.end,
+        });
+    }
+    if denom != offering.denom {
+        return Err(ContractError::WrongDenom {});
+    }
+
+    // Subscriptions are not capped, oversubscription is scaled down pro-rata at close
+    offering.subscribed += amount;
+    OFFERINGS.save(deps.storage, offering_id, &offering)?;
+    let subscription = SUBSCRIPTIONS.update(
+        deps.storage,
+        (offering_id, &investor),
+        |subscription| -> StdResult<_> { Ok(subscription.unwrap_or_default() + amount) },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "subscribe")
+        .add_attribute("offering_id", offering_id.to_string())
+        .add_attribute("investor", investor)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("subscription", subscription.to_string()))
+}
+
+// Close an offering once its subscription period has ended: issue each investor a bond
+// for their share of the raise and pay the issuer, or refund everyone below the minimum.
+// Subscriptions are settled in pages of at most `limit`, the outcome only depends on the
+// totals fixed at the end of the subscription period and every bond is issued when the
+// first page was settled.
+pub fn close_offering(
+    deps: DepsMut,
+    env: Env,
+    offering_id: u64,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut offering = load_offering(deps.as_ref(), offering_id)?;
+    if !matches!(offering.status, OfferingStatus::Open | OfferingStatus::Closing) {
+        return Err(ContractError::OfferingNotOpen { offering_id });
+    }
+    let now = env.block.time.seconds();
+    if now < offering.end {
+        return Err(ContractError::OfferingNotEnded { ends_at: offering.end });
+    }
+
+    let (subscriptions, last_page) =
+        settlement_page(deps.storage, SUBSCRIPTIONS, offering_id, &offering.closed_through, limit)?;
+    offering.closed_through = subscriptions.last().map(|(investor, _)| investor.clone());
+    let issued_at = *offering.closed_at.get_or_insert(now);
+
+    let mut msgs = vec![];
+    let mut bonds_issued = 0u64;
+    let refunded = offering.subscribed < offering.min_raise;
+    if refunded {
+        for (investor, amount) in subscriptions {
+            msgs.push(send_funds(&offering.denom, &investor, amount)?);
+        }
+    } else {
+        // Every subscription is accepted in the same proportion, rounded down
+        let accepted_total = offering.subscribed.min(offering.max_raise);
+        let mut bond_id = BOND_COUNT.load(deps.storage)?;
+        let mut raised = Uint128::zero();
+        for (investor, amount) in subscriptions {
+            let mut accepted = amount.multiply_ratio(accepted_total, offering.subscribed);
+            let face_value = accepted.multiply_ratio(
+                Decimal::one().atomics(),
+                offering.price.atomics(),
+            );
+            if face_value.is_zero() {
+                accepted = Uint128::zero();
+            } else {
+                bond_id += 1;
+                let terms = BondTerms {
+                    face_value,
+                    interest_rate: offering.interest_rate,
+                    maturity: offering.maturity,
+                    coupon_frequency: offering.coupon_frequency,
+                    day_count: offering.day_count,
+                    call_terms: None,
+                    put_terms: None,
+                    collateral: None,
+                    floating_rate: None,
+                };
+                let bond = unescrowed_bond(&offering.issuer, investor.clone(), &offering.denom, terms, issued_at);
+                BONDS.save(deps.storage, bond_id, &bond)?;
+                bonds_issued += 1;
+            }
+
+            // Return whatever part of the subscription was not accepted
+            let refund = amount - accepted;
+            if !refund.is_zero() {
+                msgs.push(send_funds(&offering.denom, &investor, refund)?);
+            }
+            raised += accepted;
+        }
+        BOND_COUNT.save(deps.storage, &bond_id)?;
+
+        // The issuer is paid what each page raised
+        if !raised.is_zero() {
+            msgs.push(send_funds(&offering.denom, &offering.issuer, raised)?);
         }
+        offering.raised += raised;
     }
+    offering.status = match (last_page, refunded) {
+        (false, _) => OfferingStatus::Closing,
+        (true, true) => OfferingStatus::Refunded,
+        (true, false) => OfferingStatus::Settled,
+    };
+    OFFERINGS.save(deps.storage, offering_id, &offering)?;
+
+    let outcome = if refunded { "refunded" } else { "settled" };
+    Ok(Response::new()
+        .add_messages(msgs)  // Pay the proceeds to the issuer and the refunds to the investors
+        .add_attribute("method", "close_offering")
+        .add_attribute("offering_id", offering_id.to_string())
+        .add_attribute("outcome", outcome)
+        .add_attribute("closed", last_page.to_string())
+        .add_attribute("raised", offering.raised.to_string())
+        .add_attribute("bonds_issued", bonds_issued.to_string()))
+}
+
+// Next page of at most `limit` entries of an offering or auction after `cursor`, with
+// whether no entries remain after it
+fn settlement_page(
+    storage: &dyn Storage,
+    entries: Map<(u64, &Addr), Uint128>,
+    id: u64,
+    cursor: &Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<(Vec<(Addr, Uint128)>, bool)> {
+    let limit = limit.unwrap_or(DEFAULT_SETTLE_LIMIT).clamp(1, MAX_SETTLE_LIMIT) as usize;
+    let start = cursor.as_ref().map(Bound::exclusive);
+    let mut page = entries
+        .prefix(id)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit + 1)
+        .collect::<StdResult<Vec<_>>>()?;
+    let last_page = page.len() <= limit;
+    page.truncate(limit);
+    Ok((page, last_page))
+}
+
+// Open a Dutch auction of bond units, charging the issuer the mint fee
+pub fn open_auction(
+    deps: DepsMut,
+    env: Env,
+    auction: Auction,
+) -> Result<Response, ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidAuction { reason: reason.to_string() };
+    if auction.supply == 0 || auction.terms.face_value.is_zero() {
+        return Err(invalid("Supply and face value must be positive"));
+    }
+    if auction.floor_price.is_zero() || auction.floor_price > auction.start_price {
+        return Err(invalid("Floor price must be positive and not above the start price"));
+    }
+    if auction.end <= auction.start.max(env.block.time.seconds()) {
+        return Err(invalid("Bidding window must end in the future and after it starts"));
+    }
+    if auction.decay == (PriceDecay::Stepwise { interval: 0 }) {
+        return Err(invalid("Price steps must have a positive interval"));
+    }
+    if auction.terms.coupon_frequency == Some(0) {
+        return Err(ContractError::InvalidCouponFrequency {});
+    }
+
+    let auction_id = AUCTION_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+
+    // Collect the platform fee in SeiX token from the issuer
+    let config = CONFIG.load(deps.storage)?;
+    let fee_msgs = collect_fee(&config, &auction.issuer, config.fees.mint, FeePayment::Allowance)?;
+
+    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+    AUCTION_COUNT.save(deps.storage, &auction_id)?;
+
+    Ok(Response::new()
+        .add_messages(fee_msgs)  // Forward the platform fee to the treasury
+        .add_attribute("method", "open_auction")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("issuer", auction.issuer))
+}
+
+// Lock funds in an open auction, refunding the part above what buys the remaining supply
+pub fn bid(
+    deps: DepsMut,
+    env: Env,
+    bidder: Addr,
+    auction_id: u64,
+    funds: (Denom, Uint128),
+) -> Result<Response, ContractError> {
+    let (denom, amount) = funds;
+    let mut auction = load_auction(deps.as_ref(), auction_id)?;
+    if auction.status != AuctionStatus::Open {
+        return Err(ContractError::AuctionNotOpen { auction_id });
+    }
+    let now = env.block.time.seconds();
+    if now < auction.start || now >= auction.end {
+        return Err(ContractError::OutsideBiddingWindow {
+            start: auction.start,
+            end: auction.end,
+        });
+    }
+    if denom != auction.denom {
+        return Err(ContractError::WrongDenom {});
+    }
+
+    // The auction sells out once the locked funds buy the whole supply at the current price
+    let remaining = sell_out_amount(&auction, now)?.saturating_sub(auction.committed);
+    if remaining.is_zero() {
+        return Err(ContractError::AuctionSoldOut { auction_id });
+    }
+    let accepted = amount.min(remaining);
+    auction.committed += accepted;
+    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+    BIDS.update(deps.storage, (auction_id, &bidder), |locked| -> StdResult<_> {
+        Ok(locked.unwrap_or_default() + accepted)
+    })?;
+
+    let refund = amount - accepted;
+    let refund_msg = if refund.is_zero() { None } else { Some(send_funds(&denom, &bidder, refund)?) };
+
+    Ok(Response::new()
+        .add_messages(refund_msg)  // Return the funds above the remaining supply
+        .add_attribute("method", "bid")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("bidder", bidder)
+        .add_attribute("accepted", accepted.to_string())
+        .add_attribute("refund", refund.to_string()))
+}
+
+// Close an auction once its bidding window has ended or it has sold out: every winner gets
+// a bond for the whole units their funds buy at the clearing price and the rest is refunded.
+// Bids are settled in pages of at most `limit`, at the clearing price of the first page
+// and with every bond issued when it was settled.
+pub fn close_auction(
+    deps: DepsMut,
+    env: Env,
+    auction_id: u64,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let mut auction = load_auction(deps.as_ref(), auction_id)?;
+    let now = env.block.time.seconds();
+    let (price, issued_at) = match (auction.status, auction.clearing_price, auction.closed_at) {
+        (AuctionStatus::Closing, Some(price), Some(closed_at)) => (price, closed_at),
+        (AuctionStatus::Open, _, _) => {
+            if now < auction.end && auction.committed < sell_out_amount(&auction, now)? {
+                return Err(ContractError::AuctionNotEnded { ends_at: auction.end });
+            }
+            (clearing_price(&auction, now), now)
+        }
+        _ => return Err(ContractError::AuctionNotOpen { auction_id }),
+    };
+
+    let (bids, last_page) = settlement_page(deps.storage, BIDS, auction_id, &auction.closed_through, limit)?;
+    auction.closed_through = bids.last().map(|(bidder, _)| bidder.clone());
+
+    let mut msgs = vec![];
+    let mut bond_id = BOND_COUNT.load(deps.storage)?;
+    let mut units_sold = Uint128::zero();
+    let mut proceeds = Uint128::zero();
+    for (bidder, locked) in bids {
+        let units = locked / price;
+        let cost = units * price;
+        if !units.is_zero() {
+            bond_id += 1;
+            let terms = BondTerms {
+                face_value: auction.terms.face_value.checked_mul(units).map_err(StdError::from)?,
+                ..auction.terms.clone()
+            };
+            let bond = unescrowed_bond(&auction.issuer, bidder.clone(), &auction.denom, terms, issued_at);
+            BONDS.save(deps.storage, bond_id, &bond)?;
+        }
+
+        let refund = locked - cost;
+        if !refund.is_zero() {
+            msgs.push(send_funds(&auction.denom, &bidder, refund)?);
+        }
+        units_sold += units;
+        proceeds += cost;
+    }
+    BOND_COUNT.save(deps.storage, &bond_id)?;
+
+    if !proceeds.is_zero() {
+        msgs.push(send_funds(&auction.denom, &auction.issuer, proceeds)?);
+    }
+    auction.clearing_price = Some(price);
+    auction.closed_at = Some(issued_at);
+    auction.status = if last_page { AuctionStatus::Settled } else { AuctionStatus::Closing };
+    AUCTIONS.save(deps.storage, auction_id, &auction)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)  // Pay the proceeds to the issuer and the refunds to the bidders
+        .add_attribute("method", "close_auction")
+        .add_attribute("auction_id", auction_id.to_string())
+        .add_attribute("closed", last_page.to_string())
+        .add_attribute("clearing_price", price.to_string())
+        .add_attribute("units_sold", units_sold.to_string())
+        .add_attribute("proceeds", proceeds.to_string()))
+}
+
+// Unit price of an auction at a timestamp, decaying from the start price to the floor
+fn auction_price(auction: &Auction, time: u64) -> Uint128 {
+    let duration = auction.end - auction.start;
+    let elapsed = time.clamp(auction.start, auction.end) - auction.start;
+    let elapsed = match auction.decay {
+        PriceDecay::Linear => elapsed,
+        // The price holds until the end of each step, the floor is reached at the end
+        PriceDecay::Stepwise { interval } if elapsed < duration => elapsed - elapsed % interval,
+        PriceDecay::Stepwise { .. } => elapsed,
+    };
+    auction.start_price - (auction.start_price - auction.floor_price).multiply_ratio(elapsed, duration)
+}
+
+// Funds that buy the whole supply of an auction at its price at a timestamp
+fn sell_out_amount(auction: &Auction, time: u64) -> StdResult<Uint128> {
+    Ok(auction_price(auction, time).checked_mul(Uint128::from(auction.supply))?)
+}
+
+// Price every winner pays: the current price, or once sold out the price at which the locked
+// funds buy the whole supply, rounded up so that no more than the supply is sold
+fn clearing_price(auction: &Auction, time: u64) -> Uint128 {
+    let sold_out_price = auction.committed.u128().div_ceil(u128::from(auction.supply));
+    auction_price(auction, time).max(Uint128::new(sold_out_price))
+}
+
+// Bond sold through an offering or auction, nothing is escrowed until the issuer repays it
+fn unescrowed_bond(issuer: &Addr, holder: Addr, denom: &Denom, terms: BondTerms, issued_at: u64) -> Bond {
+    Bond {
+        issuer: issuer.clone(),
+        face_value: terms.face_value,
+        interest_rate: terms.interest_rate,
+        maturity: terms.maturity,
+        holder,
+        issued_at,
+        denom: denom.clone(),
+        escrow: Uint128::zero(),
+        coupon_frequency: terms.coupon_frequency,
+        coupons_paid: 0,
+        approvals: vec![],
+        day_count: terms.day_count,
+        call_terms: terms.call_terms,
+        call_date: None,
+        put_terms: terms.put_terms,
+        collateral: terms.collateral,
+        status: BondStatus::Pending,
+        floating_rate: terms.floating_rate,
+    }
+}
+
+// Announce that a bond will be called once its notice period has passed
+pub fn notice_call(
+    deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    bond_id: u64,
+) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+    if bond.issuer != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let call_terms = bond.call_terms.as_ref().ok_or(ContractError::NotCallable { bond_id })?;
+    if let Some(call_date) = bond.call_date {
+        return Err(ContractError::CallNoticeGiven { call_date });
+    }
+
+    // Notice may be given during the call protection, the call then follows right after it
+    let call_date = (env.block.time.seconds() + call_terms.notice)
+        .max(bond.issued_at + call_terms.protection);
+    let matures_at = bond.issued_at + bond.maturity;
+    if call_date >= matures_at {
+        return Err(ContractError::CallAfterMaturity { matures_at });
+    }
+    let price = scheduled_price(&call_terms.schedule, call_date - bond.issued_at);
+    bond.call_date = Some(call_date);
+    BONDS.save(deps.storage, bond_id, &bond)?;
+
+    let event = Event::new("bond_call_notice")
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("holder", bond.holder)
+        .add_attribute("call_date", call_date.to_string())
+        .add_attribute("call_price", price.to_string());
+    Ok(Response::new()
+        .add_event(event)
+        .add_attribute("method", "notice_call")
+        .add_attribute("bond_id", bond_id.to_string()))
+}
+
+// Redeem a callable bond early: pay the holder the call price plus the unpaid accrued
+// interest and return whatever is left in escrow to the issuer
+pub fn call_bond(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    bond_id: u64,
+    funds: Option<(Denom, Uint128)>,
+) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+    if bond.issuer != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let call_terms = bond.call_terms.as_ref().ok_or(ContractError::NotCallable { bond_id })?;
+
+    let now = env.block.time.seconds();
+    let matures_at = bond.issued_at + bond.maturity;
+    if now >= matures_at {
+        return Err(ContractError::CallAfterMaturity { matures_at });
+    }
+    // Without a notice period the bond can be called as soon as its protection ends
+    match bond.call_date {
+        Some(call_date) if now < call_date => {
+            return Err(ContractError::CallNotDue { call_date });
+        }
+        None if call_terms.notice > 0 => return Err(ContractError::CallNoticeRequired {}),
+        _ => {}
+    }
+    let protected_until = bond.issued_at + call_terms.protection;
+    if now < protected_until {
+        return Err(ContractError::CallProtected { until: protected_until });
+    }
+
+    let mut available = bond.escrow;
+    if let Some((denom, amount)) = funds {
+        if denom != bond.denom {
+            return Err(ContractError::WrongDenom {});
+        }
+        available += amount;
+    }
+
+    // The holder is paid the price announced for the call date, even when called later
+    let elapsed = now - bond.issued_at;
+    let priced_at = bond.call_date.map_or(elapsed, |call_date| call_date - bond.issued_at);
+    let price = scheduled_price(&call_terms.schedule, priced_at);
+    let (fixings, fixing_events) = fix_rates(deps.branch(), bond_id, &mut bond, now)?;
+    let interest = unpaid_interest(&bond, &fixings, elapsed);
+    let payout = bond.face_value.mul_floor(price) + interest;
+    if payout > available {
+        return Err(ContractError::InsufficientFunds { needed: payout, available });
+    }
+
+    let mut msgs = vec![send_funds(&bond.denom, &bond.holder, payout)?];
+    let remainder = available - payout;
+    if !remainder.is_zero() {
+        msgs.push(send_funds(&bond.denom, &bond.issuer, remainder)?);
+    }
+    msgs.extend(release_collateral(&bond)?);
+    close_bond(deps.storage, bond_id, &mut bond, BondStatus::Called)?;
+
+    let event = Event::new("bond_called")
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("holder", bond.holder)
+        .add_attribute("call_price", price.to_string())
+        .add_attribute("accrued_interest", interest.to_string())
+        .add_attribute("payout", payout.to_string());
+    Ok(Response::new()
+        .add_messages(msgs)  // Pay the holder, return the rest of the escrow and the collateral to the issuer
+        .add_events(fixing_events)
+        .add_event(event)
+        .add_attribute("method", "call_bond")
+        .add_attribute("bond_id", bond_id.to_string()))
+}
+
+// Check that call provisions fit within the life of the bond
+fn validate_call_terms(call_terms: &CallTerms, maturity: u64) -> Result<(), ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidCallTerms { reason: reason.to_string() };
+    if call_terms.protection >= maturity {
+        return Err(invalid("Call protection must end before maturity"));
+    }
+    validate_schedule(&call_terms.schedule).map_err(invalid)
+}
+
+// Redeem a puttable bond early, in whole or for part of its face value: pay the holder the
+// put price plus the unpaid accrued interest on the part put, then return to the issuer
+// whatever is left in escrow beyond what they still owe on the rest
+pub fn put_bond(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    fee: FeePayment,
+    bond_id: u64,
+    amount: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+    if bond.holder != sender {
+        return Err(ContractError::NotHolder { action: "put".to_string() });
+    }
+    let put_terms = bond.put_terms.as_ref().ok_or(ContractError::NotPuttable { bond_id })?;
+
+    let now = env.block.time.seconds();
+    let elapsed = now.saturating_sub(bond.issued_at);
+    if !put_terms.windows.iter().any(|window| window.start <= elapsed && elapsed < window.end) {
+        return Err(ContractError::OutsidePutWindow {});
+    }
+    let amount = amount.unwrap_or(bond.face_value);
+    if amount.is_zero() || amount > bond.face_value {
+        return Err(ContractError::InvalidPutAmount { amount, face_value: bond.face_value });
+    }
+
+    // Interest is linear in the face value, so the part put accrues as a bond of its own
+    let price = scheduled_price(&put_terms.schedule, elapsed);
+    let put_part = Bond { face_value: amount, ..bond.clone() };
+    let (fixings, fixing_events) = fix_rates(deps.branch(), bond_id, &mut bond, now)?;
+    let interest = unpaid_interest(&put_part, &fixings, elapsed);
+    let payout = amount.mul_floor(price) + interest;
+    if payout > bond.escrow {
+        return Err(ContractError::InsufficientFunds { needed: payout, available: bond.escrow });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let fee_msgs = collect_fee(&config, &sender, config.fees.redeem, fee)?;
+    let mut msgs = vec![send_funds(&bond.denom, &bond.holder, payout)?];
+
+    bond.escrow -= payout;
+    bond.face_value -= amount;
+    let owed = bond.face_value + unpaid_interest(&bond, &fixings, bond.maturity);
+    let released = bond.escrow.saturating_sub(owed);
+    if !released.is_zero() {
+        msgs.push(send_funds(&bond.denom, &bond.issuer, released)?);
+        bond.escrow -= released;
+    }
+
+    // Orders were priced for the bond as it was
+    if bond.face_value.is_zero() {
+        msgs.extend(release_collateral(&bond)?);
+        close_bond(deps.storage, bond_id, &mut bond, BondStatus::Redeemed)?;
+    } else {
+        BONDS.save(deps.storage, bond_id, &bond)?;
+        cancel_bond_orders(deps.storage, bond_id)?;
+    }
+
+    let event = Event::new("bond_put")
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("holder", bond.holder)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("put_price", price.to_string())
+        .add_attribute("accrued_interest", interest.to_string())
+        .add_attribute("payout", payout.to_string())
+        .add_attribute("released", released.to_string());
+    Ok(Response::new()
+        .add_messages(fee_msgs)  // Forward the platform fee to the treasury
+        .add_messages(msgs)  // Pay the holder and release the surplus escrow to the issuer
+        .add_events(fixing_events)
+        .add_event(event)
+        .add_attribute("method", "put_bond")
+        .add_attribute("bond_id", bond_id.to_string()))
+}
+
+// Lock more collateral against a bond
+pub fn top_up_collateral(
+    deps: DepsMut,
+    sender: Addr,
+    bond_id: u64,
+    funds: (Denom, Uint128),
+) -> Result<Response, ContractError> {
+    let (denom, amount) = funds;
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+    let collateral = bond.collateral.as_mut().ok_or(ContractError::NotCollateralized { bond_id })?;
+    if denom != collateral.denom {
+        return Err(ContractError::WrongDenom {});
+    }
+    collateral.amount += amount;
+    let locked = collateral.amount;
+    BONDS.save(deps.storage, bond_id, &bond)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "top_up_collateral")
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("funder", sender)
+        .add_attribute("amount", amount.to_string())
+        .add_attribute("collateral", locked.to_string()))
+}
+
+// Return the collateral locked beyond what the collateral ratio requires to the issuer
+pub fn withdraw_excess_collateral(
+    deps: DepsMut,
+    sender: Addr,
+    bond_id: u64,
+) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+    if bond.issuer != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    let face_value = bond.face_value;
+    let collateral = bond.collateral.as_mut().ok_or(ContractError::NotCollateralized { bond_id })?;
+    let excess = collateral.amount.saturating_sub(face_value.mul_ceil(collateral.ratio));
+    if excess.is_zero() {
+        return Err(ContractError::NoExcessCollateral {});
+    }
+    collateral.amount -= excess;
+    let withdraw_msg = send_funds(&collateral.denom, &bond.issuer, excess)?;
+    BONDS.save(deps.storage, bond_id, &bond)?;
+
+    Ok(Response::new()
+        .add_message(withdraw_msg)
+        .add_attribute("method", "withdraw_excess_collateral")
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("amount", excess.to_string()))
+}
+
+// Settle a bond in default: the holder gets what is in escrow and the share of the collateral
+// matching the part of the bond left unpaid, the issuer gets the rest of the collateral back.
+// The holder of an unsecured bond only gets the escrow.
+pub fn claim_collateral(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    bond_id: u64,
+) -> Result<Response, ContractError> {
+    let mut bond = load_bond(deps.as_ref(), bond_id)?;
+    if bond.holder != sender {
+        return Err(ContractError::NotHolder { action: "liquidate".to_string() });
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let (fixings, fixing_events) = fix_rates(deps.branch(), bond_id, &mut bond, now)?;
+    let default_event = ensure_default(deps.storage, &config, bond_id, &mut bond, &fixings, now)?;
+    let mut record = DEFAULTS.load(deps.storage, (&bond.issuer, bond_id))?;
+    if record.resolution.is_some() {
+        return Err(ContractError::DefaultResolved { bond_id });
+    }
+
+    let mut msgs = vec![];
+    if !bond.escrow.is_zero() {
+        msgs.push(send_funds(&bond.denom, &bond.holder, bond.escrow)?);
+    }
+    let (mut claimed, mut returned) = (Uint128::zero(), Uint128::zero());
+    if let Some(collateral) = &bond.collateral {
+        let owed = record.shortfall + bond.escrow;
+        claimed = collateral.amount.multiply_ratio(record.shortfall, owed);
+        returned = collateral.amount - claimed;
+        if !claimed.is_zero() {
+            msgs.push(send_funds(&collateral.denom, &bond.holder, claimed)?);
+        }
+        if !returned.is_zero() {
+            msgs.push(send_funds(&collateral.denom, &bond.issuer, returned)?);
+        }
+    }
+    record.resolution = Some(DefaultResolution::Liquidated { at: now, claimed });
+    DEFAULTS.save(deps.storage, (&bond.issuer, bond_id), &record)?;
+    let bond_escrow = bond.escrow;
+    settle_bond(deps.storage, bond_id, &mut bond)?;
+
+    let event = Event::new("collateral_claimed")
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("holder", bond.holder)
+        .add_attribute("shortfall", record.shortfall.to_string())
+        .add_attribute("escrow", bond_escrow.to_string())
+        .add_attribute("claimed", claimed.to_string())
+        .add_attribute("returned", returned.to_string());
+    Ok(Response::new()
+        .add_messages(msgs)  // Pay the holder from escrow and collateral, return the rest to the issuer
+        .add_events(fixing_events)
+        .add_events(default_event)
+        .add_event(event)
+        .add_attribute("method", "claim_collateral")
+        .add_attribute("bond_id", bond_id.to_string()))
+}
+
+// Put a bond that was not repaid by the end of its grace period in default
+pub fn mark_default(mut deps: DepsMut, env: Env, bond_id: u64) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let (fixings, fixing_events) = fix_rates(deps.branch(), bond_id, &mut bond, now)?;
+    let event = ensure_default(deps.storage, &config, bond_id, &mut bond, &fixings, now)?;
+
+    Ok(Response::new()
+        .add_events(fixing_events)
+        .add_events(event)
+        .add_attribute("method", "mark_default")
+        .add_attribute("bond_id", bond_id.to_string()))
+}
+
+// Repay a bond in default: the holder is paid the face value and interest plus penalty
+// interest on the shortfall since maturity, the issuer gets the collateral back
+pub fn cure_default(
+    mut deps: DepsMut,
+    env: Env,
+    sender: Addr,
+    bond_id: u64,
+    funds: (Denom, Uint128),
+) -> Result<Response, ContractError> {
+    let (denom, amount) = funds;
+    let mut bond = load_bond(deps.as_ref(), bond_id)?;
+    if bond.issuer != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if denom != bond.denom {
+        return Err(ContractError::WrongDenom {});
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time.seconds();
+    let (fixings, fixing_events) = fix_rates(deps.branch(), bond_id, &mut bond, now)?;
+    let default_event = ensure_default(deps.storage, &config, bond_id, &mut bond, &fixings, now)?;
+    let mut record = DEFAULTS.load(deps.storage, (&bond.issuer, bond_id))?;
+    if record.resolution.is_some() {
+        return Err(ContractError::DefaultResolved { bond_id });
+    }
+
+    let penalty = interest_due(
+        record.shortfall,
+        config.default_policy.penalty_rate,
+        bond.day_count,
+        bond.issued_at + bond.maturity,
+        now,
+    );
+    let needed = record.shortfall + penalty;
+    if amount < needed {
+        return Err(ContractError::InsufficientFunds { needed, available: amount });
+    }
+    let payout = bond.escrow + needed;
+    let mut msgs = vec![send_funds(&bond.denom, &bond.holder, payout)?];
+    let refund = amount - needed;
+    if !refund.is_zero() {
+        msgs.push(send_funds(&bond.denom, &sender, refund)?);
+    }
+    msgs.extend(release_collateral(&bond)?);
+    record.resolution = Some(DefaultResolution::Cured { at: now, penalty });
+    DEFAULTS.save(deps.storage, (&bond.issuer, bond_id), &record)?;
+    close_bond(deps.storage, bond_id, &mut bond, BondStatus::Redeemed)?;
+
+    let event = Event::new("default_cured")
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("holder", bond.holder)
+        .add_attribute("penalty", penalty.to_string())
+        .add_attribute("payout", payout.to_string());
+    Ok(Response::new()
+        .add_messages(msgs)  // Pay the holder, refund the surplus and return the collateral
+        .add_events(fixing_events)
+        .add_events(default_event)
+        .add_event(event)
+        .add_attribute("method", "cure_default")
+        .add_attribute("bond_id", bond_id.to_string()))
+}
+
+// Put a bond in default once its grace period is over and it is still not repaid in full,
+// recording the default in its issuer's history and cancelling its open orders. Returns the
+// event announcing the default, or nothing when the bond was already in default.
+fn ensure_default(
+    storage: &mut dyn Storage,
+    config: &Config,
+    bond_id: u64,
+    bond: &mut Bond,
+    fixings: &[RateFixing],
+    now: u64,
+) -> Result<Option<Event>, ContractError> {
+    if bond.status == BondStatus::Defaulted {
+        return Ok(None);
+    }
+    if !bond.status.is_outstanding() {
+        return Err(ContractError::BondClosed { bond_id, status: bond.status });
+    }
+    let ends_at = bond.issued_at + bond.maturity + config.default_policy.grace_period;
+    if now <= ends_at {
+        return Err(ContractError::GracePeriodNotOver { ends_at });
+    }
+    let owed = bond.face_value + unpaid_interest(bond, fixings, bond.maturity);
+    if bond.escrow >= owed {
+        return Err(ContractError::BondRepaid {});
+    }
+
+    let shortfall = owed - bond.escrow;
+    set_status(bond, BondStatus::Defaulted)?;
+    BONDS.save(storage, bond_id, bond)?;
+    cancel_bond_orders(storage, bond_id)?;
+    let record = DefaultRecord { bond_id, defaulted_at: now, shortfall, resolution: None };
+    DEFAULTS.save(storage, (&bond.issuer, bond_id), &record)?;
+
+    Ok(Some(
+        Event::new("bond_defaulted")
+            .add_attribute("bond_id", bond_id.to_string())
+            .add_attribute("issuer", bond.issuer.clone())
+            .add_attribute("shortfall", shortfall.to_string()),
+    ))
+}
+
+// Cancel a bond the issuer still holds, returning its escrow and collateral to them
+pub fn cancel_bond(deps: DepsMut, sender: Addr, bond_id: u64) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+    if bond.issuer != sender || bond.holder != sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut msgs = vec![];
+    if !bond.escrow.is_zero() {
+        msgs.push(send_funds(&bond.denom, &bond.issuer, bond.escrow)?);
+    }
+    msgs.extend(release_collateral(&bond)?);
+    close_bond(deps.storage, bond_id, &mut bond, BondStatus::Cancelled)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)  // Return the escrow and collateral to the issuer
+        .add_attribute("method", "cancel_bond")
+        .add_attribute("bond_id", bond_id.to_string()))
+}
+
+// Fix the rate of the coupon periods of a floating-rate bond that have started, so that the
+// coupons are set even when nothing else touches the bond
+pub fn fix_rate(mut deps: DepsMut, env: Env, bond_id: u64) -> Result<Response, ContractError> {
+    let mut bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+    if bond.floating_rate.is_none() {
+        return Err(ContractError::NotFloating { bond_id });
+    }
+    let (_, events) = fix_rates(deps.branch(), bond_id, &mut bond, env.block.time.seconds())?;
+    if events.is_empty() {
+        return Err(ContractError::NoRateToFix {});
+    }
+
+    Ok(Response::new()
+        .add_events(events)
+        .add_attribute("method", "fix_rate")
+        .add_attribute("bond_id", bond_id.to_string()))
+}
+
+// Return the collateral locked against a settled bond to its issuer
+fn release_collateral(bond: &Bond) -> StdResult<Vec<CosmosMsg>> {
+    match &bond.collateral {
+        Some(collateral) if !collateral.amount.is_zero() => {
+            Ok(vec![send_funds(&collateral.denom, &bond.issuer, collateral.amount)?])
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+// Check that a floating rate can be fixed every coupon period and its floor is below a positive cap
+fn validate_floating_rate(floating_rate: &FloatingRate, terms: &BondTerms) -> Result<(), ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidFloatingRate { reason: reason.to_string() };
+    if terms.coupon_frequency.is_none() {
+        return Err(invalid("A floating-rate bond must pay coupons"));
+    }
+    if floating_rate.cap.is_zero() {
+        return Err(invalid("The cap must be positive"));
+    }
+    if floating_rate.floor > floating_rate.cap {
+        return Err(invalid("The floor cannot be above the cap"));
+    }
+    if !terms.interest_rate.is_zero() {
+        return Err(invalid("The interest rate of a floating-rate bond is set by its fixings, leave it at zero"));
+    }
+    Ok(())
+}
+
+// Check that the collateral locked at mint covers the collateral ratio
+fn validate_collateral(collateral: &Collateral, face_value: Uint128) -> Result<(), ContractError> {
+    if collateral.ratio.is_zero() {
+        return Err(ContractError::InvalidCollateral {
+            reason: "Collateral ratio must be positive".to_string(),
+        });
+    }
+    let required = face_value.mul_ceil(collateral.ratio);
+    if collateral.amount < required {
+        return Err(ContractError::Undercollateralized { required, locked: collateral.amount });
+    }
+    Ok(())
+}
+
+// Coin funding a bond minted with native coins, after setting aside the native collateral
+// attached to the same message
+fn principal_coin(info: &MessageInfo, collateral: Option<&Collateral>) -> Result<Coin, ContractError> {
+    let Some(Collateral { denom: Denom::Native(denom), amount, .. }) = collateral else {
+        return Ok(one_coin(info)?);
+    };
+    let missing = || ContractError::InvalidCollateral {
+        reason: format!("{amount}{denom} of collateral must be attached"),
+    };
+    let mut funds = info.funds.clone();
+    let locked = funds.iter_mut().find(|coin| coin.denom == *denom).ok_or_else(missing)?;
+    locked.amount = locked.amount.checked_sub(*amount).map_err(|_| missing())?;
+    funds.retain(|coin| !coin.amount.is_zero());
+    match funds.as_slice() {
+        [coin] => Ok(coin.clone()),
+        [] => Err(PaymentError::NoFunds {}.into()),
+        _ => Err(PaymentError::MultipleDenoms {}.into()),
+    }
+}
+
+// Check that put provisions fit within the life of the bond
+fn validate_put_terms(put_terms: &PutTerms, maturity: u64) -> Result<(), ContractError> {
+    let invalid = |reason: &str| ContractError::InvalidPutTerms { reason: reason.to_string() };
+    if put_terms.windows.is_empty() {
+        return Err(invalid("Put windows must not be empty"));
+    }
+    if put_terms.windows.iter().any(|window| window.start >= window.end || window.end > maturity) {
+        return Err(invalid("Put windows must open before they close, no later than maturity"));
+    }
+    if put_terms.windows.windows(2).any(|pair| pair[0].end > pair[1].start) {
+        return Err(invalid("Put windows must be in ascending order and not overlap"));
+    }
+    validate_schedule(&put_terms.schedule).map_err(invalid)
+}
+
+// Check a call or put price schedule, returning the reason it is invalid
+fn validate_schedule(schedule: &[RedemptionPrice]) -> Result<(), &'static str> {
+    if schedule.is_empty() {
+        return Err("Price schedule must not be empty");
+    }
+    if schedule.windows(2).any(|pair| pair[0].from >= pair[1].from) {
+        return Err("Price schedule must be in ascending order");
+    }
+    if schedule.iter().any(|entry| entry.price.is_zero()) {
+        return Err("Prices must be positive");
+    }
+    Ok(())
+}
+
+// Price `elapsed` seconds after issuance: the last schedule entry started by then,
+// or the first entry before the schedule starts
+fn scheduled_price(schedule: &[RedemptionPrice], elapsed: u64) -> Decimal {
+    schedule
+        .iter()
+        .rev()
+        .find(|entry| entry.from <= elapsed)
+        .or(schedule.first())
+        .map(|entry| entry.price)
+        .unwrap_or_default()
+}
+
+// List a bond for sale at an ask price
+pub fn list_bond(
+    deps: DepsMut,
+    env: Env,
+    seller: Addr,
+    bond_id: u64,
+    denom: Denom,
+    price: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let bond = load_outstanding_bond(deps.as_ref(), bond_id)?;
+    if bond.holder != seller {
+        return Err(ContractError::NotHolder { action: "list".to_string() });
+    }
+
+    let invalid = |reason: &str| ContractError::InvalidOrder { reason: reason.to_string() };
+    if price.is_zero() {
+        return Err(invalid("Ask price must be positive"));
+    }
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(invalid("Cannot list an order that is already expired"));
+    }
+
+    let order_id = ORDER_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    let order = SellOrder { bond_id, seller, denom, price, expires };
+    ORDERS.save(deps.storage, order_id, &order)?;
+    ORDER_COUNT.save(deps.storage, &order_id)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "list_bond")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("bond_id", bond_id.to_string())
+        .add_attribute("price", price.to_string()))
+}
+
+// Fill an order: pay the seller, collect the transfer fee and hand the bond to the buyer
+pub fn buy_bond(
+    deps: DepsMut,
+    env: Env,
+    buyer: Addr,
+    order_id: u64,
+    payment: (Denom, Uint128),
+    fee: FeePayment,
+) -> Result<Response, ContractError> {
+    let (denom, amount) = payment;
+    let order = load_order(deps.as_ref(), order_id)?;
+    if order.expires.is_expired(&env.block) {
+        return Err(ContractError::OrderExpired { order_id });
+    }
+    if denom != order.denom {
+        return Err(ContractError::WrongDenom {});
+    }
+    if amount != order.price {
+        return Err(ContractError::InvalidPayment { price: order.price, received: amount });
+    }
+
+    // Collect the platform fee in SeiX token from the buyer
+    let config = CONFIG.load(deps.storage)?;
+    let fee_msgs = collect_fee(&config, &buyer, config.fees.transfer, fee)?;
+
+    // The sale closes every order on the bond, approvals do not carry over to the buyer
+    let mut bond = load_outstanding_bond(deps.as_ref(), order.bond_id)?;
+    bond.holder = buyer.clone();
+    bond.approvals = vec![];
+    BONDS.save(deps.storage, order.bond_id, &bond)?;
+    cancel_bond_orders(deps.storage, order.bond_id)?;
+
+    let payment_msg = send_funds(&order.denom, &order.seller, order.price)?;
+
+    Ok(Response::new()
+        .add_messages(fee_msgs)  // Forward the platform fee to the treasury
+        .add_message(payment_msg)  // Pay the seller
+        .add_attribute("method", "buy_bond")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("bond_id", order.bond_id.to_string())
+        .add_attribute("seller", order.seller)
+        .add_attribute("buyer", buyer)
+        .add_attribute("price", order.price.to_string()))
+}
+
+// Remove an open order, only the seller can cancel it
+pub fn cancel_order(
+    deps: DepsMut,
+    info: MessageInfo,
+    order_id: u64,
+) -> Result<Response, ContractError> {
+    let order = load_order(deps.as_ref(), order_id)?;
+    if order.seller != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    ORDERS.remove(deps.storage, order_id)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "cancel_order")
+        .add_attribute("order_id", order_id.to_string())
+        .add_attribute("bond_id", order.bond_id.to_string()))
+}
+
+// Remove every open order on a bond once it changes hands or is redeemed
+fn cancel_bond_orders(storage: &mut dyn Storage, bond_id: u64) -> StdResult<()> {
+    let order_ids = ORDERS
+        .idx
+        .bond
+        .prefix(bond_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for order_id in order_ids {
+        ORDERS.remove(storage, order_id)?;
+    }
+    Ok(())
+}
+
+// Ensure SeiX-only operations were sent from the configured fee token
+fn ensure_fee_token(config: &Config, token: &Addr) -> Result<(), ContractError> {
+    if *token != config.fee_token {
+        return Err(ContractError::WrongFeeToken {});
+    }
+    Ok(())
+}
+
+// The sender reported in a Send hook is only taken at its word, for pulling their allowance,
+// when the hook comes from the fee token or a token the admin trusts
+fn ensure_trusted_token(config: &Config, token: &Addr) -> Result<(), ContractError> {
+    if *token != config.fee_token && !config.trusted_tokens.contains(token) {
+        return Err(ContractError::UntrustedToken { token: token.to_string() });
+    }
+    Ok(())
+}
+
+// Build the CW20 message moving a platform fee to the treasury, if any is due
+fn collect_fee(
+    config: &Config,
+    payer: &Addr,
+    fee: Uint128,
+    payment: FeePayment,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    let transfer_msg = match payment {
+        // The fee was already sent to the contract, it must match the schedule exactly
+        FeePayment::Sent(amount) => {
+            if amount != fee {
+                return Err(ContractError::InvalidFee { expected: fee, received: amount });
+            }
+            Cw20ExecuteMsg::Transfer {
+                recipient: config.treasury.to_string(),
+                amount: fee,
+            }
+        }
+        // Otherwise pull it from the payer, which must have approved the contract
+        FeePayment::Allowance => Cw20ExecuteMsg::TransferFrom {
+            owner: payer.to_string(),
+            recipient: config.treasury.to_string(),
+            amount: fee,
+        },
+    };
+    if fee.is_zero() {
+        return Ok(None);
+    }
+    Ok(Some(
+        WasmMsg::Execute {
+            contract_addr: config.fee_token.to_string(),
+            msg: to_json_binary(&transfer_msg)?,
+            funds: vec![],
+        }
+        .into(),
+    ))
+}
+
+// Interest owed between two timestamps using simple annual interest under the day-count
+// convention, rounded down to whole units of the denomination
+pub fn interest_due(
+    face_value: Uint128,
+    interest_rate: Decimal,
+    day_count: DayCount,
+    start: u64,
+    end: u64,
+) -> Uint128 {
+    let (accrual, year) = accrual_period(day_count, start, end);
+    face_value.multiply_ratio(
+        interest_rate.atomics() * Uint128::from(accrual),
+        Decimal::one().atomics() * Uint128::from(year),
+    )
+}
+
+// Accrual time between two timestamps and the length of a year under the convention, in seconds
+fn accrual_period(day_count: DayCount, start: u64, end: u64) -> (u64, u64) {
+    let end = end.max(start);
+    match day_count {
+        DayCount::Act365 => (end - start, 365 * SECONDS_PER_DAY),
+        DayCount::Act360 => (end - start, 360 * SECONDS_PER_DAY),
+        DayCount::Thirty360 => (days_30_360(start, end) * SECONDS_PER_DAY, 360 * SECONDS_PER_DAY),
+    }
+}
+
+// Whole days between two timestamps with every month counted as 30 days (bond basis)
+fn days_30_360(start: u64, end: u64) -> u64 {
+    let (y1, m1, d1) = civil_date(start);
+    let (y2, m2, d2) = civil_date(end);
+    let d1 = d1.min(30);
+    let d2 = if d1 == 30 { d2.min(30) } else { d2 };
+    (360 * (y2 - y1) + 30 * (m2 - m1) + (d2 - d1)).max(0) as u64
+}
+
+// UTC calendar date (year, month, day) of a unix timestamp
+fn civil_date(timestamp: u64) -> (i64, i64, i64) {
+    // Days are counted from 0000-03-01 so that leap days fall at the end of the year
+    let days = (timestamp / SECONDS_PER_DAY) as i64 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+// Interest accrued on a bond `elapsed` seconds after issuance, stopping at maturity.
+// A floating-rate bond accrues each coupon period at the rate in its fixing, or at the cap
+// for periods without one.
+fn accrued_interest(bond: &Bond, fixings: &[RateFixing], elapsed: u64) -> Uint128 {
+    let elapsed = elapsed.min(bond.maturity);
+    let Some(floating_rate) = &bond.floating_rate else {
+        let end = bond.issued_at + elapsed;
+        return interest_due(bond.face_value, bond.interest_rate, bond.day_count, bond.issued_at, end);
+    };
+    (1..=coupon_count(bond))
+        .map(|period| (period, coupon_offset(bond, period - 1)))
+        .take_while(|&(_, start)| start < elapsed)
+        .map(|(period, start)| {
+            let end = coupon_offset(bond, period).min(elapsed);
+            let rate = fixings.get(period as usize - 1).map_or(floating_rate.cap, |fixing| fixing.rate);
+            interest_due(bond.face_value, rate, bond.day_count, bond.issued_at + start, bond.issued_at + end)
+        })
+        .sum()
+}
+
+// Interest accrued `elapsed` seconds after issuance that has not been paid out as coupons
+fn unpaid_interest(bond: &Bond, fixings: &[RateFixing], elapsed: u64) -> Uint128 {
+    accrued_interest(bond, fixings, elapsed)
+        .saturating_sub(accrued_interest(bond, fixings, coupon_offset(bond, bond.coupons_paid)))
+}
+
+// Seconds after issuance at which the given coupon period (counted from 1) ends
+fn coupon_offset(bond: &Bond, period: u64) -> u64 {
+    match bond.coupon_frequency {
+        Some(frequency) => period.saturating_mul(frequency).min(bond.maturity),
+        None => 0,
+    }
+}
+
+// Number of coupon periods over the life of the bond, the last one may be shorter
+fn coupon_count(bond: &Bond) -> u64 {
+    match bond.coupon_frequency {
+        Some(frequency) => bond.maturity.div_ceil(frequency),
+        None => 0,
+    }
+}
+
+// Number of coupon periods that have ended by `now`
+fn coupons_due(bond: &Bond, now: u64) -> u64 {
+    let elapsed = now.saturating_sub(bond.issued_at);
+    match bond.coupon_frequency {
+        Some(_) if elapsed >= bond.maturity => coupon_count(bond),
+        Some(frequency) => elapsed / frequency,
+        None => 0,
+    }
+}
+
+// Number of coupon periods that have started by `now`
+fn coupons_started(bond: &Bond, now: u64) -> u64 {
+    let elapsed = now.saturating_sub(bond.issued_at);
+    match bond.coupon_frequency {
+        Some(frequency) => (elapsed / frequency + 1).min(coupon_count(bond)),
+        None => 0,
+    }
+}
+
+// Amount owed for the unpaid coupon periods up to and including `due`
+fn coupon_amount(bond: &Bond, fixings: &[RateFixing], due: u64) -> Uint128 {
+    accrued_interest(bond, fixings, coupon_offset(bond, due))
+        - accrued_interest(bond, fixings, coupon_offset(bond, bond.coupons_paid))
+}
+
+// Current reference rate published by the rate oracle
+fn query_reference_rate(deps: Deps) -> Result<Decimal, ContractError> {
+    let oracle = CONFIG.load(deps.storage)?.rate_oracle.ok_or(ContractError::NoRateOracle {})?;
+    let response: ReferenceRateResponse =
+        deps.querier.query_wasm_smart(oracle, &OracleQueryMsg::ReferenceRate {})?;
+    Ok(response.rate)
+}
+
+// Rate fixings saved for a floating-rate bond, in period order
+fn load_fixings(storage: &dyn Storage, bond_id: u64) -> StdResult<Vec<RateFixing>> {
+    RATE_FIXINGS
+        .prefix(bond_id)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, fixing)| fixing))
+        .collect()
+}
+
+// Fixings for the coupon periods of a floating-rate bond that have started by `now` and come
+// after the fixings it already has. Only the period running at `now` is fixed from the
+// oracle, so that a period cannot be fixed after it ended at a rate chosen by whoever calls.
+// Periods that ended unfixed, and the running one when the oracle cannot be read, keep the
+// rate of the period before them, or the cap for the first period.
+fn pending_fixings(deps: Deps, bond: &Bond, fixings: &[RateFixing], now: u64) -> Vec<RateFixing> {
+    let Some(floating_rate) = &bond.floating_rate else {
+        return vec![];
+    };
+    let next = fixings.len() as u64 + 1;
+    let started = coupons_started(bond, now);
+    let running = now < bond.issued_at + bond.maturity;
+    let mut previous = fixings.last().cloned();
+    let mut pending = vec![];
+    for period in next..=started {
+        let reference_rate = if period == started && running {
+            query_reference_rate(deps).ok()
+        } else {
+            None
+        };
+        let fixing = match (reference_rate, &previous) {
+            (Some(reference_rate), _) => RateFixing {
+                period,
+                fixed_at: now,
+                reference_rate,
+                rate: floating_rate.rate(reference_rate),
+                source: FixingSource::Oracle,
+            },
+            (None, Some(previous)) => RateFixing {
+                period,
+                fixed_at: now,
+                source: FixingSource::Previous,
+                ..previous.clone()
+            },
+            (None, None) => RateFixing {
+                period,
+                fixed_at: now,
+                reference_rate: Decimal::zero(),
+                rate: floating_rate.cap,
+                source: FixingSource::Cap,
+            },
+        };
+        previous = Some(fixing.clone());
+        pending.push(fixing);
+    }
+    pending
+}
+
+// Fix the rate of the coupon periods of a floating-rate bond that have started by `now`,
+// saving the bond with the rate of its latest fixing. Returns every fixing of the bond, none
+// for a fixed-rate bond, and the events announcing the new ones.
+fn fix_rates(
+    deps: DepsMut,
+    bond_id: u64,
+    bond: &mut Bond,
+    now: u64,
+) -> Result<(Vec<RateFixing>, Vec<Event>), ContractError> {
+    if bond.floating_rate.is_none() {
+        return Ok((vec![], vec![]));
+    }
+    let mut fixings = load_fixings(deps.storage, bond_id)?;
+    let mut events = vec![];
+    for fixing in pending_fixings(deps.as_ref(), bond, &fixings, now) {
+        RATE_FIXINGS.save(deps.storage, (bond_id, fixing.period), &fixing)?;
+        events.push(
+            Event::new("rate_fixed")
+                .add_attribute("bond_id", bond_id.to_string())
+                .add_attribute("period", fixing.period.to_string())
+                .add_attribute("source", fixing.source.to_string())
+                .add_attribute("reference_rate", fixing.reference_rate.to_string())
+                .add_attribute("rate", fixing.rate.to_string()),
+        );
+        fixings.push(fixing);
+    }
+    if let Some(latest) = fixings.last().filter(|_| !events.is_empty()) {
+        bond.interest_rate = latest.rate;
+        BONDS.save(deps.storage, bond_id, bond)?;
+    }
+    Ok((fixings, events))
+}
+
+// Fixings of a bond as they would stand at `now`, without saving anything. Periods that have
+// started and were not fixed yet are projected as they would be fixed now.
+fn projected_fixings(deps: Deps, bond_id: u64, bond: &Bond, now: u64) -> Result<Vec<RateFixing>, ContractError> {
+    if bond.floating_rate.is_none() {
+        return Ok(vec![]);
+    }
+    let mut fixings = load_fixings(deps.storage, bond_id)?;
+    let pending = pending_fixings(deps, bond, &fixings, now);
+    fixings.extend(pending);
+    Ok(fixings)
+}
+
+// Build the message paying out escrowed funds in the bond's denomination
+fn send_funds(denom: &Denom, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    let msg = match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin { denom: denom.clone(), amount }],
+        }
+        .into(),
+        Denom::Cw20(contract_addr) => WasmMsg::Execute {
+            contract_addr: contract_addr.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    };
+    Ok(msg)
+}
+
+// Query contract state
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetBond { bond_id } => {
+            let bond = load_bond(deps, bond_id)?;
+            to_json_binary(&bond_response(bond, env.block.time.seconds()))
+        }
+        QueryMsg::NextCoupon { bond_id } => to_json_binary(&query_next_coupon(deps, env, bond_id)?),
+        QueryMsg::AccruedInterest { bond_id, at_time } => {
+            to_json_binary(&query_accrued_interest(deps, env, bond_id, at_time)?)
+        }
+        QueryMsg::OwnerOf { token_id, include_expired } => {
+            to_json_binary(&query_owner_of(deps, env, token_id, include_expired.unwrap_or(false))?)
+        }
+        QueryMsg::NftInfo { token_id } => to_json_binary(&query_nft_info(deps, token_id)?),
+        QueryMsg::Tokens { owner, start_after, limit } => {
+            to_json_binary(&query_tokens(deps, Some(owner), start_after, limit)?)
+        }
+        QueryMsg::AllTokens { start_after, limit } => {
+            to_json_binary(&query_tokens(deps, None, start_after, limit)?)
+        }
+        QueryMsg::ListBonds { start_after, limit } => {
+            to_json_binary(&query_list_bonds(deps, env, start_after, limit)?)
+        }
+        QueryMsg::BondsByHolder { holder, start_after, limit } => {
+            to_json_binary(&query_bonds_by_holder(deps, env, holder, start_after, limit)?)
+        }
+        QueryMsg::BondsByIssuer { issuer, start_after, limit } => {
+            to_json_binary(&query_bonds_by_issuer(deps, env, issuer, start_after, limit)?)
+        }
+        QueryMsg::BondsMaturingBetween { from, to, start_after, limit } => {
+            to_json_binary(&query_bonds_maturing_between(deps, env, from, to, start_after, limit)?)
+        }
+        QueryMsg::BondsByStatus { status, start_after, limit } => {
+            to_json_binary(&query_bonds_by_status(deps, env, status, start_after, limit)?)
+        }
+        QueryMsg::RateFixings { bond_id, start_after, limit } => {
+            to_json_binary(&query_rate_fixings(deps, bond_id, start_after, limit)?)
+        }
+        QueryMsg::DefaultsByIssuer { issuer, start_after, limit } => {
+            to_json_binary(&query_defaults_by_issuer(deps, issuer, start_after, limit)?)
+        }
+        QueryMsg::Offering { offering_id } => {
+            let offering = load_offering(deps, offering_id)?;
+            to_json_binary(&OfferingResponse::from(offering))
+        }
+        QueryMsg::Subscription { offering_id, investor } => {
+            to_json_binary(&query_subscription(deps, offering_id, investor)?)
+        }
+        QueryMsg::Auction { auction_id } => {
+            let auction = load_auction(deps, auction_id)?;
+            to_json_binary(&AuctionResponse::from(auction))
+        }
+        QueryMsg::AuctionPrice { auction_id } => {
+            to_json_binary(&query_auction_price(deps, env, auction_id)?)
+        }
+        QueryMsg::Order { order_id } => {
+            let order = load_order(deps, order_id)?;
+            to_json_binary(&OrderResponse::new(order_id, order))
+        }
+        QueryMsg::OrdersByBond { bond_id, start_after, limit } => {
+            to_json_binary(&query_orders_by_bond(deps, env, bond_id, start_after, limit)?)
+        }
+        QueryMsg::OrdersBySeller { seller, start_after, limit } => {
+            to_json_binary(&query_orders_by_seller(deps, env, seller, start_after, limit)?)
+        }
+        QueryMsg::OrdersByPrice { denom, max_price, start_after, limit } => {
+            to_json_binary(&query_orders_by_price(deps, env, denom, max_price, start_after, limit)?)
+        }
+        QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
+    }
+}
+
+// Bond as returned by queries, with its status at `now`
+fn bond_response(bond: Bond, now: u64) -> BondResponse {
+    let status = bond.status_at(now);
+    BondResponse { status, ..bond.into() }
+}
+
+// Collect a page of bonds from an iterator over (bond ID, bond) records
+fn collect_bonds(
+    records: impl Iterator<Item = StdResult<(u64, Bond)>>,
+    limit: Option<u32>,
+    now: u64,
+) -> StdResult<BondsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let bonds = records
+        .take(limit)
+        .map(|item| {
+            item.map(|(bond_id, bond)| BondInfo {
+                bond_id,
+                bond: bond_response(bond, now),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(BondsResponse { bonds })
+}
+
+fn query_list_bonds(
+    deps: Deps,
+    env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BondsResponse> {
+    let start = start_after.map(Bound::exclusive);
+    let records = BONDS.range(deps.storage, start, None, Order::Ascending);
+    collect_bonds(records, limit, env.block.time.seconds())
+}
+
+fn query_bonds_by_holder(
+    deps: Deps,
+    env: Env,
+    holder: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BondsResponse> {
+    let holder = validate_addr(deps.api, "holder", &holder)?;
+    let start = start_after.map(Bound::exclusive);
+    let records = BONDS
+        .idx
+        .holder
+        .prefix(holder)
+        .range(deps.storage, start, None, Order::Ascending);
+    collect_bonds(records, limit, env.block.time.seconds())
+}
+
+fn query_bonds_by_issuer(
+    deps: Deps,
+    env: Env,
+    issuer: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BondsResponse> {
+    let issuer = validate_addr(deps.api, "issuer", &issuer)?;
+    let start = start_after.map(Bound::exclusive);
+    let records = BONDS
+        .idx
+        .issuer
+        .prefix(issuer)
+        .range(deps.storage, start, None, Order::Ascending);
+    collect_bonds(records, limit, env.block.time.seconds())
+}
+
+fn query_bonds_maturing_between(
+    deps: Deps,
+    env: Env,
+    from: u64,
+    to: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BondsResponse> {
+    // Resume after the given bond within its maturity, otherwise start at `from`
+    let start = match start_after {
+        Some(bond_id) => {
+            let bond = load_bond(deps, bond_id)?;
+            Bound::exclusive((bond.issued_at + bond.maturity, bond_id))
+        }
+        None => Bound::inclusive((from, 0)),
+    };
+    let records = BONDS
+        .idx
+        .maturity
+        .range(deps.storage, Some(start), None, Order::Ascending)
+        .take_while(|item| match item {
+            Ok((_, bond)) => bond.issued_at + bond.maturity <= to,
+            Err(_) => true,
+        });
+    collect_bonds(records, limit, env.block.time.seconds())
+}
+
+fn query_bonds_by_status(
+    deps: Deps,
+    env: Env,
+    status: BondStatus,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<BondsResponse> {
+    let now = env.block.time.seconds();
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    // Matured bonds are stored as pending or active, take a page of each and merge them
+    let stored = match status {
+        BondStatus::Matured => vec![BondStatus::Pending, BondStatus::Active],
+        status => vec![status],
+    };
+    let mut bonds = vec![];
+    for stored_status in stored {
+        let records = BONDS
+            .idx
+            .status
+            .prefix(stored_status.to_string())
+            .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+            .filter(|item| !matches!(item, Ok((_, bond)) if bond.status_at(now) != status))
+            .take(limit);
+        for item in records {
+            bonds.push(item?);
+        }
+    }
+    bonds.sort_by_key(|(bond_id, _)| *bond_id);
+    bonds.truncate(limit);
+    collect_bonds(bonds.into_iter().map(Ok), Some(limit as u32), now)
+}
+
+fn query_defaults_by_issuer(
+    deps: Deps,
+    issuer: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<DefaultsResponse> {
+    let issuer = validate_addr(deps.api, "issuer", &issuer)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let defaults = DEFAULTS
+        .prefix(&issuer)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, record)| record))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(DefaultsResponse { defaults })
+}
+
+fn query_rate_fixings(
+    deps: Deps,
+    bond_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RateFixingsResponse> {
+    load_bond(deps, bond_id)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let fixings = RATE_FIXINGS
+        .prefix(bond_id)
+        .range(deps.storage, start_after.map(Bound::exclusive), None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, fixing)| fixing))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(RateFixingsResponse { fixings })
+}
+
+// Collect a page of open orders from an iterator over (order ID, order) records,
+// skipping expired orders
+fn collect_orders(
+    block: &BlockInfo,
+    records: impl Iterator<Item = StdResult<(u64, SellOrder)>>,
+    limit: Option<u32>,
+) -> StdResult<OrdersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let orders = records
+        .filter(|item| !matches!(item, Ok((_, order)) if order.expires.is_expired(block)))
+        .take(limit)
+        .map(|item| item.map(|(order_id, order)| OrderResponse::new(order_id, order)))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(OrdersResponse { orders })
+}
+
+fn query_orders_by_bond(
+    deps: Deps,
+    env: Env,
+    bond_id: u64,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OrdersResponse> {
+    let start = start_after.map(Bound::exclusive);
+    let records = ORDERS
+        .idx
+        .bond
+        .prefix(bond_id)
+        .range(deps.storage, start, None, Order::Ascending);
+    collect_orders(&env.block, records, limit)
+}
+
+fn query_orders_by_seller(
+    deps: Deps,
+    env: Env,
+    seller: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OrdersResponse> {
+    let seller = validate_addr(deps.api, "seller", &seller)?;
+    let start = start_after.map(Bound::exclusive);
+    let records = ORDERS
+        .idx
+        .seller
+        .prefix(seller)
+        .range(deps.storage, start, None, Order::Ascending);
+    collect_orders(&env.block, records, limit)
+}
+
+fn query_orders_by_price(
+    deps: Deps,
+    env: Env,
+    denom: Denom,
+    max_price: Option<Uint128>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<OrdersResponse> {
+    // Resume after the given order within its price, otherwise start at the lowest price
+    let key = denom_key(&denom);
+    let start = match start_after {
+        Some(order_id) => {
+            let order = load_order(deps, order_id)?;
+            Bound::exclusive(((key, order.price.u128()), order_id))
+        }
+        None => Bound::inclusive(((key, 0), 0)),
+    };
+    let records = ORDERS
+        .idx
+        .price
+        .range(deps.storage, Some(start), None, Order::Ascending)
+        .take_while(|item| match item {
+            Ok((_, order)) => {
+                order.denom == denom && max_price.is_none_or(|max_price| order.price <= max_price)
+            }
+            Err(_) => true,
+        });
+    collect_orders(&env.block, records, limit)
+}
+
+fn query_owner_of(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: bool,
+) -> StdResult<OwnerOfResponse> {
+    let bond = load_bond(deps, parse_token_id(&token_id)?)?;
+    let approvals = bond
+        .approvals
+        .into_iter()
+        .filter(|approval| include_expired || !approval.is_expired(&env.block))
+        .collect();
+    Ok(OwnerOfResponse {
+        owner: bond.holder.to_string(),
+        approvals,
+    })
+}
+
+fn query_nft_info(deps: Deps, token_id: String) -> StdResult<NftInfoResponse<BondMetadata>> {
+    let bond = load_bond(deps, parse_token_id(&token_id)?)?;
+    Ok(NftInfoResponse {
+        token_uri: None,
+        extension: BondMetadata {
+            issuer: bond.issuer,
+            face_value: bond.face_value,
+            interest_rate: bond.interest_rate,
+            maturity: bond.maturity,
+            issued_at: bond.issued_at,
+            denom: bond.denom,
+            coupon_frequency: bond.coupon_frequency,
+            day_count: bond.day_count,
+            call_terms: bond.call_terms,
+            put_terms: bond.put_terms,
+            collateral: bond.collateral,
+            floating_rate: bond.floating_rate,
+        },
+    })
+}
+
+// List bond ids, optionally only those held by `owner`
+fn query_tokens(
+    deps: Deps,
+    owner: Option<String>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .map(|token_id| parse_token_id(&token_id))
+        .transpose()?
+        .map(Bound::exclusive);
+
+    let bonds = match owner {
+        Some(owner) => BONDS
+            .idx
+            .holder
+            .prefix(validate_addr(deps.api, "owner", &owner)?)
+            .range(deps.storage, start, None, Order::Ascending),
+        None => BONDS.range(deps.storage, start, None, Order::Ascending),
+    };
+    // Settled bonds stay on record but are no longer tokens
+    let tokens = bonds
+        .filter(|item| !matches!(item, Ok((_, bond)) if !bond.status.is_outstanding()))
+        .take(limit)
+        .map(|item| item.map(|(bond_id, _)| bond_id.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
+fn query_next_coupon(deps: Deps, env: Env, bond_id: u64) -> StdResult<NextCouponResponse> {
+    let bond = load_bond(deps, bond_id)?;
+    let now = env.block.time.seconds();
+    let due = coupons_due(&bond, now);
+    let fixings = projected_fixings(deps, bond_id, &bond, now)?;
+
+    // The next coupon is the first period that has not been paid yet
+    let next_coupon_date = if bond.coupons_paid < coupon_count(&bond) {
+        Some(bond.issued_at + coupon_offset(&bond, bond.coupons_paid + 1))
+    } else {
+        None
+    };
+
+    Ok(NextCouponResponse {
+        next_coupon_date,
+        coupons_due: due.saturating_sub(bond.coupons_paid),
+        amount_due: if due > bond.coupons_paid { coupon_amount(&bond, &fixings, due) } else { Uint128::zero() },
+    })
+}
+
+fn query_accrued_interest(
+    deps: Deps,
+    env: Env,
+    bond_id: u64,
+    at_time: Option<u64>,
+) -> StdResult<AccruedInterestResponse> {
+    let bond = load_bond(deps, bond_id)?;
+    let at_time = at_time.unwrap_or_else(|| env.block.time.seconds());
+    let fixings = projected_fixings(deps, bond_id, &bond, at_time)?;
+    let accrued_interest = unpaid_interest(&bond, &fixings, at_time.saturating_sub(bond.issued_at));
+
+    Ok(AccruedInterestResponse {
+        at_time,
+        day_count: bond.day_count,
+        accrued_interest,
+        redemption_amount: bond.face_value + accrued_interest,
+        rounding: RoundingPolicy::Floor,
+    })
+}
+
+fn query_subscription(
+    deps: Deps,
+    offering_id: u64,
+    investor: String,
+) -> StdResult<SubscriptionResponse> {
+    load_offering(deps, offering_id)?;
+    let investor = validate_addr(deps.api, "investor", &investor)?;
+    let amount = SUBSCRIPTIONS
+        .may_load(deps.storage, (offering_id, &investor))?
+        .unwrap_or_default();
+    Ok(SubscriptionResponse { amount })
+}
+
+fn query_auction_price(deps: Deps, env: Env, auction_id: u64) -> StdResult<AuctionPriceResponse> {
+    let auction = load_auction(deps, auction_id)?;
+
+    // A settled auction keeps its clearing price, bidding stops at the end of the window
+    let now = env.block.time.seconds().min(auction.end);
+    let price = auction.clearing_price.unwrap_or_else(|| clearing_price(&auction, now));
+    let filled = (auction.committed / price).min(Uint128::from(auction.supply));
+
+    Ok(AuctionPriceResponse {
+        price,
+        committed: auction.committed,
+        filled: filled.u128() as u64,
+        supply: auction.supply,
+        sold_out: auction.committed >= sell_out_amount(&auction, now)?,
+    })
 }
 
-// Helper function to convert to binary for queries
-fn to_binary<T: serde::Serialize>(obj: &T) -> StdResult<Binary> {
-    cosmwasm_std::to_binary(obj)
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    Ok(ConfigResponse {
+        admin: config.admin,
+        treasury: config.treasury,
+        fee_token: config.fee_token,
+        fees: config.fees,
+        default_policy: config.default_policy,
+        rate_oracle: config.rate_oracle,
+        trusted_tokens: config.trusted_tokens,
+    })
 }
@@ -1,13 +1,277 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
+use crate::state::BondStatus;
+use cw_utils::PaymentError;
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+// Errors returned by the contract. A failed transaction carries no response attributes,
+// so every error message starts with its stable code (see `ContractError::code`)
+// for clients to branch on.
+#[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
-    #[error("{0}")]
+    #[error("STD: {0}")]
     Std(#[from] StdError),
 
-    #[error("Unauthorized")]
+    #[error("PAYMENT: {0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("UNAUTHORIZED: Unauthorized")]
     Unauthorized {},
-    // Add any other custom errors you like here.
-    // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+
+    #[error("INVALID_ADDRESS: Invalid address in `{field}` ({address}): {reason}")]
+    InvalidAddress {
+        field: String,
+        address: String,
+        reason: String,
+    },
+
+    #[error("BOND_NOT_FOUND: Bond {bond_id} does not exist")]
+    BondNotFound { bond_id: u64 },
+
+    #[error("INVALID_TOKEN_ID: Invalid token id: {token_id}")]
+    InvalidTokenId { token_id: String },
+
+    #[error("BOND_CLOSED: Bond {bond_id} is {status}")]
+    BondClosed { bond_id: u64, status: BondStatus },
+
+    #[error("INVALID_TRANSITION: Bond cannot go from {from} to {to}")]
+    InvalidTransition { from: BondStatus, to: BondStatus },
+
+    #[error("NOT_HOLDER: Only the bond holder can {action} the bond")]
+    NotHolder { action: String },
+
+    #[error("NOT_MATURED: Bond has not matured yet, it matures at {matures_at}")]
+    NotMatured { matures_at: u64 },
+
+    #[error("INSUFFICIENT_FUNDS: Insufficient funds: needed {needed}, available {available}")]
+    InsufficientFunds { needed: Uint128, available: Uint128 },
+
+    #[error("INVALID_FUNDING: Bond must be funded with exactly {required} (face value plus interest), got {received}")]
+    InvalidFunding { required: Uint128, received: Uint128 },
+
+    #[error("INVALID_FEE: Invalid platform fee: expected {expected}, got {received}")]
+    InvalidFee { expected: Uint128, received: Uint128 },
+
+    #[error("WRONG_FEE_TOKEN: Platform fees must be paid in the SeiX token")]
+    WrongFeeToken {},
+
+    #[error("INVALID_COUPON_FREQUENCY: Coupon frequency must be positive")]
+    InvalidCouponFrequency {},
+
+    #[error("NO_COUPON_DUE: No coupon is due")]
+    NoCouponDue {},
+
+    #[error("EXPIRED_APPROVAL: Cannot set approval that is already expired")]
+    ExpiredApproval {},
+
+    #[error("WRONG_DENOM: Funds were sent in a different denomination than required")]
+    WrongDenom {},
+
+    #[error("OVERFUNDED: Only {outstanding} is still owed on the bond, got {received}")]
+    Overfunded { outstanding: Uint128, received: Uint128 },
+
+    #[error("OFFERING_NOT_FOUND: Offering {offering_id} does not exist")]
+    OfferingNotFound { offering_id: u64 },
+
+    #[error("INVALID_OFFERING: {reason}")]
+    InvalidOffering { reason: String },
+
+    #[error("OFFERING_NOT_OPEN: Offering {offering_id} is closed")]
+    OfferingNotOpen { offering_id: u64 },
+
+    #[error("OUTSIDE_SUBSCRIPTION_PERIOD: Subscriptions are accepted from {start} until {end}")]
+    OutsideSubscriptionPeriod { start: u64, end: u64 },
+
+    #[error("OFFERING_NOT_ENDED: Subscriptions are open until {ends_at}")]
+    OfferingNotEnded { ends_at: u64 },
+
+    #[error("AUCTION_NOT_FOUND: Auction {auction_id} does not exist")]
+    AuctionNotFound { auction_id: u64 },
+
+    #[error("INVALID_AUCTION: {reason}")]
+    InvalidAuction { reason: String },
+
+    #[error("AUCTION_NOT_OPEN: Auction {auction_id} is closed")]
+    AuctionNotOpen { auction_id: u64 },
+
+    #[error("OUTSIDE_BIDDING_WINDOW: Bids are accepted from {start} until {end}")]
+    OutsideBiddingWindow { start: u64, end: u64 },
+
+    #[error("AUCTION_SOLD_OUT: Auction {auction_id} is sold out")]
+    AuctionSoldOut { auction_id: u64 },
+
+    #[error("AUCTION_NOT_ENDED: Bids are accepted until {ends_at} unless the auction sells out")]
+    AuctionNotEnded { ends_at: u64 },
+
+    #[error("ORDER_NOT_FOUND: Order {order_id} does not exist")]
+    OrderNotFound { order_id: u64 },
+
+    #[error("INVALID_ORDER: {reason}")]
+    InvalidOrder { reason: String },
+
+    #[error("ORDER_EXPIRED: Order {order_id} has expired")]
+    OrderExpired { order_id: u64 },
+
+    #[error("INVALID_PAYMENT: The bond is sold for {price}, got {received}")]
+    InvalidPayment { price: Uint128, received: Uint128 },
+
+    #[error("INVALID_CALL_TERMS: {reason}")]
+    InvalidCallTerms { reason: String },
+
+    #[error("NOT_CALLABLE: Bond {bond_id} has no call provisions")]
+    NotCallable { bond_id: u64 },
+
+    #[error("CALL_PROTECTED: Bond cannot be called before {until}")]
+    CallProtected { until: u64 },
+
+    #[error("CALL_AFTER_MATURITY: Bond cannot be called from its maturity at {matures_at}")]
+    CallAfterMaturity { matures_at: u64 },
+
+    #[error("CALL_NOTICE_GIVEN: Bond is already being called at {call_date}")]
+    CallNoticeGiven { call_date: u64 },
+
+    #[error("CALL_NOTICE_REQUIRED: Notice must be given before the bond is called")]
+    CallNoticeRequired {},
+
+    #[error("CALL_NOT_DUE: Bond can only be called from {call_date}")]
+    CallNotDue { call_date: u64 },
+
+    #[error("INVALID_PUT_TERMS: {reason}")]
+    InvalidPutTerms { reason: String },
+
+    #[error("NOT_PUTTABLE: Bond {bond_id} has no put provisions")]
+    NotPuttable { bond_id: u64 },
+
+    #[error("OUTSIDE_PUT_WINDOW: Bond can only be put during its put windows")]
+    OutsidePutWindow {},
+
+    #[error("INVALID_PUT_AMOUNT: Cannot put {amount} of a bond with face value {face_value}")]
+    InvalidPutAmount { amount: Uint128, face_value: Uint128 },
+
+    #[error("INVALID_COLLATERAL: {reason}")]
+    InvalidCollateral { reason: String },
+
+    #[error("UNDERCOLLATERALIZED: Collateral of {locked} is below the {required} required")]
+    Undercollateralized { required: Uint128, locked: Uint128 },
+
+    #[error("NOT_COLLATERALIZED: Bond {bond_id} has no collateral")]
+    NotCollateralized { bond_id: u64 },
+
+    #[error("NO_EXCESS_COLLATERAL: No collateral is locked beyond what the bond requires")]
+    NoExcessCollateral {},
+
+    #[error("GRACE_PERIOD_NOT_OVER: Bond is in its grace period until {ends_at}")]
+    GracePeriodNotOver { ends_at: u64 },
+
+    #[error("GRACE_PERIOD_OVER: The grace period ended at {ended_at}, the bond must be repaid with CureDefault")]
+    GracePeriodOver { ended_at: u64 },
+
+    #[error("DEFAULT_RESOLVED: The default on bond {bond_id} was already resolved")]
+    DefaultResolved { bond_id: u64 },
+
+    #[error("BOND_REPAID: Bond is repaid in full, redeem it instead")]
+    BondRepaid {},
+
+    #[error("INVALID_FLOATING_RATE: {reason}")]
+    InvalidFloatingRate { reason: String },
+
+    #[error("NO_RATE_ORACLE: No rate oracle is configured for floating-rate bonds")]
+    NoRateOracle {},
+
+    #[error("NOT_FLOATING: Bond {bond_id} pays a fixed rate")]
+    NotFloating { bond_id: u64 },
+
+    #[error("NO_RATE_TO_FIX: Every coupon period that has started already has its rate fixed")]
+    NoRateToFix {},
+
+    #[error("UNTRUSTED_TOKEN: Token {token} is not trusted to report who sent it")]
+    UntrustedToken { token: String },
+
+    #[error("WRONG_CONTRACT: Cannot migrate from a different contract: {contract}")]
+    WrongContract { contract: String },
+
+    #[error("DOWNGRADE: Cannot migrate from newer version {from} to {to}")]
+    Downgrade { from: String, to: String },
+
+    #[error("LEGACY_DENOM_REQUIRED: Bonds from before 0.2.0 need a legacy_denom to be repaid in")]
+    LegacyDenomRequired {},
+
+    #[error("LEGACY_CONFIG_REQUIRED: Migrating from before 0.2.0 needs a legacy_config")]
+    LegacyConfigRequired {},
+}
+
+impl ContractError {
+    // Stable code identifying the error, also the prefix of its message
+    pub fn code(&self) -> &'static str {
+        match self {
+            ContractError::Std(_) => "STD",
+            ContractError::Payment(_) => "PAYMENT",
+            ContractError::Unauthorized {} => "UNAUTHORIZED",
+            ContractError::InvalidAddress { .. } => "INVALID_ADDRESS",
+            ContractError::BondNotFound { .. } => "BOND_NOT_FOUND",
+            ContractError::InvalidTokenId { .. } => "INVALID_TOKEN_ID",
+            ContractError::BondClosed { .. } => "BOND_CLOSED",
+            ContractError::InvalidTransition { .. } => "INVALID_TRANSITION",
+            ContractError::NotHolder { .. } => "NOT_HOLDER",
+            ContractError::NotMatured { .. } => "NOT_MATURED",
+            ContractError::InsufficientFunds { .. } => "INSUFFICIENT_FUNDS",
+            ContractError::InvalidFunding { .. } => "INVALID_FUNDING",
+            ContractError::InvalidFee { .. } => "INVALID_FEE",
+            ContractError::WrongFeeToken {} => "WRONG_FEE_TOKEN",
+            ContractError::InvalidCouponFrequency {} => "INVALID_COUPON_FREQUENCY",
+            ContractError::NoCouponDue {} => "NO_COUPON_DUE",
+            ContractError::ExpiredApproval {} => "EXPIRED_APPROVAL",
+            ContractError::WrongDenom {} => "WRONG_DENOM",
+            ContractError::Overfunded { .. } => "OVERFUNDED",
+            ContractError::OfferingNotFound { .. } => "OFFERING_NOT_FOUND",
+            ContractError::InvalidOffering { .. } => "INVALID_OFFERING",
+            ContractError::OfferingNotOpen { .. } => "OFFERING_NOT_OPEN",
+            ContractError::OutsideSubscriptionPeriod { .. } => "OUTSIDE_SUBSCRIPTION_PERIOD",
+            ContractError::OfferingNotEnded { .. } => "OFFERING_NOT_ENDED",
+            ContractError::AuctionNotFound { .. } => "AUCTION_NOT_FOUND",
+            ContractError::InvalidAuction { .. } => "INVALID_AUCTION",
+            ContractError::AuctionNotOpen { .. } => "AUCTION_NOT_OPEN",
+            ContractError::OutsideBiddingWindow { .. } => "OUTSIDE_BIDDING_WINDOW",
+            ContractError::AuctionSoldOut { .. } => "AUCTION_SOLD_OUT",
+            ContractError::AuctionNotEnded { .. } => "AUCTION_NOT_ENDED",
+            ContractError::OrderNotFound { .. } => "ORDER_NOT_FOUND",
+            ContractError::InvalidOrder { .. } => "INVALID_ORDER",
+            ContractError::OrderExpired { .. } => "ORDER_EXPIRED",
+            ContractError::InvalidPayment { .. } => "INVALID_PAYMENT",
+            ContractError::InvalidCallTerms { .. } => "INVALID_CALL_TERMS",
+            ContractError::NotCallable { .. } => "NOT_CALLABLE",
+            ContractError::CallProtected { .. } => "CALL_PROTECTED",
+            ContractError::CallAfterMaturity { .. } => "CALL_AFTER_MATURITY",
+            ContractError::CallNoticeGiven { .. } => "CALL_NOTICE_GIVEN",
+            ContractError::CallNoticeRequired {} => "CALL_NOTICE_REQUIRED",
+            ContractError::CallNotDue { .. } => "CALL_NOT_DUE",
+            ContractError::InvalidPutTerms { .. } => "INVALID_PUT_TERMS",
+            ContractError::NotPuttable { .. } => "NOT_PUTTABLE",
+            ContractError::OutsidePutWindow {} => "OUTSIDE_PUT_WINDOW",
+            ContractError::InvalidPutAmount { .. } => "INVALID_PUT_AMOUNT",
+            ContractError::InvalidCollateral { .. } => "INVALID_COLLATERAL",
+            ContractError::Undercollateralized { .. } => "UNDERCOLLATERALIZED",
+            ContractError::NotCollateralized { .. } => "NOT_COLLATERALIZED",
+            ContractError::NoExcessCollateral {} => "NO_EXCESS_COLLATERAL",
+            ContractError::GracePeriodNotOver { .. } => "GRACE_PERIOD_NOT_OVER",
+            ContractError::GracePeriodOver { .. } => "GRACE_PERIOD_OVER",
+            ContractError::DefaultResolved { .. } => "DEFAULT_RESOLVED",
+            ContractError::BondRepaid {} => "BOND_REPAID",
+            ContractError::InvalidFloatingRate { .. } => "INVALID_FLOATING_RATE",
+            ContractError::NoRateOracle {} => "NO_RATE_ORACLE",
+            ContractError::NotFloating { .. } => "NOT_FLOATING",
+            ContractError::NoRateToFix {} => "NO_RATE_TO_FIX",
+            ContractError::UntrustedToken { .. } => "UNTRUSTED_TOKEN",
+            ContractError::WrongContract { .. } => "WRONG_CONTRACT",
+            ContractError::Downgrade { .. } => "DOWNGRADE",
+            ContractError::LegacyDenomRequired {} => "LEGACY_DENOM_REQUIRED",
+            ContractError::LegacyConfigRequired {} => "LEGACY_CONFIG_REQUIRED",
+        }
+    }
+}
+
+// Queries return `StdResult`, keep the message (which starts with the code) when they fail
+impl From<ContractError> for StdError {
+    fn from(err: ContractError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
 }
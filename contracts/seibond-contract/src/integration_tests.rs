@@ -1,17 +1,30 @@
 #[cfg(test)]
 mod tests {
-    use crate::msg::{InstantiateMsg, ExecuteMsg};
+    use crate::msg::{ConfigResponse, InstantiateMsg, ExecuteMsg, NextCouponResponse, QueryMsg, ReceiveMsg};
     use crate::helpers::CwTemplateContract;
-    use crate::state::Bond;
+    use crate::ContractError;
+    use crate::state::{BondStatus, BondTerms, DayCount, DefaultPolicy, FeeSchedule, RedemptionPrice};
     use cosmwasm_std::testing::MockApi;
-    use cosmwasm_std::{Addr, Coin, Empty, Uint128};
-    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+    use cosmwasm_std::{to_json_binary, Addr, Coin, Decimal, Empty, Response, StdError, Uint128};
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg, Denom};
+    use cw_multi_test::error::AnyResult;
+    use cw_multi_test::{App, AppBuilder, AppResponse, Contract, ContractWrapper, Executor};
 
     pub fn contract_template() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(
             crate::contract::execute,
             crate::contract::instantiate,
             crate::contract::query,
+        )
+        .with_migrate(crate::contract::migrate);
+        Box::new(contract)
+    }
+
+    pub fn contract_seix() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
         );
         Box::new(contract)
     }
@@ -20,7 +33,15 @@ mod tests {
     const ADMIN: &str = "ADMIN";
     const PLATFORM_TREASURY: &str = "platform_treasury";
     const NATIVE_DENOM: &str = "denom";
-    const SEIX_TOKEN: &str = "seix_token"; // Token address for SeiX
+
+    // A one year bond paying 10% on a face value of 100_000
+    const FACE_VALUE: u128 = 100_000;
+    const INTEREST_RATE: Decimal = Decimal::percent(10);
+    const MATURITY: u64 = 365 * 24 * 60 * 60;
+    const REQUIRED_FUNDING: u128 = 110_000;
+
+    const DAY: u64 = 24 * 60 * 60;
+    const HOLDER: &str = "holder";
 
     fn mock_app() -> App {
         AppBuilder::new().build(|router, _, storage| {
@@ -31,40 +52,242 @@ mod tests {
                     &MockApi::default().addr_make(USER),
                     vec![Coin {
                         denom: NATIVE_DENOM.to_string(),
-                        amount: Uint128::new(1),
+                        amount: Uint128::new(1_000_000),
                     }],
                 )
                 .unwrap();
         })
     }
 
-    fn proper_instantiate() -> (App, CwTemplateContract) {
+    fn proper_instantiate() -> (App, CwTemplateContract, Addr) {
         let mut app = mock_app();
         let cw_template_id = app.store_code(contract_template());
+        let seix_id = app.store_code(contract_seix());
 
         let user = app.api().addr_make(USER);
         assert_eq!(
-            app.wrap().query_balance(user, NATIVE_DENOM).unwrap().amount,
-            Uint128::new(1)
+            app.wrap().query_balance(&user, NATIVE_DENOM).unwrap().amount,
+            Uint128::new(1_000_000)
         );
 
+        // The SeiX token, with the supply split between the user and the admin
+        let admin = app.api().addr_make(ADMIN);
+        let seix_addr = app
+            .instantiate_contract(
+                seix_id,
+                admin.clone(),
+                &cw20_base::msg::InstantiateMsg {
+                    name: "SeiX".to_string(),
+                    symbol: "SEIX".to_string(),
+                    decimals: 6,
+                    initial_balances: vec![
+                        Cw20Coin {
+                            address: user.to_string(),
+                            amount: Uint128::new(1_000_000),
+                        },
+                        Cw20Coin {
+                            address: admin.to_string(),
+                            amount: Uint128::new(1_000_000),
+                        },
+                    ],
+                    mint: None,
+                    marketing: None,
+                },
+                &[],
+                "seix",
+                None,
+            )
+            .unwrap();
+
         let msg = InstantiateMsg {
-            seix_contract: SEIX_TOKEN.to_string(), // Use SeiX token contract address
+            seix_contract: seix_addr.to_string(), // Use SeiX token contract address
+            treasury: app.api().addr_make(PLATFORM_TREASURY).to_string(),
+            fees: FeeSchedule {
+                mint: Uint128::new(10),
+                transfer: Uint128::new(5),
+                redeem: Uint128::new(15),
+            },
+            admin: None,
+            default_policy: None,
+            rate_oracle: None,
+            trusted_tokens: None,
         };
         let cw_template_contract_addr = app
             .instantiate_contract(
                 cw_template_id,
-                Addr::unchecked(ADMIN),
+                app.api().addr_make(ADMIN),
                 &msg,
                 &[],
                 "test",
-                None,
+                Some(admin.to_string()),
             )
             .unwrap();
 
         let cw_template_contract = CwTemplateContract(cw_template_contract_addr);
 
-        (app, cw_template_contract)
+        (app, cw_template_contract, seix_addr)
+    }
+
+    // Allow the bond contract to pull the mint fee from the user's SeiX balance
+    fn approve_mint_fee(app: &mut App, cw_template_contract: &CwTemplateContract) {
+        let config: ConfigResponse = app
+            .wrap()
+            .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::Config {})
+            .unwrap();
+        let user = app.api().addr_make(USER);
+        app.execute_contract(
+            user,
+            config.fee_token,
+            &Cw20ExecuteMsg::IncreaseAllowance {
+                spender: cw_template_contract.addr().to_string(),
+                amount: config.fees.mint,
+                expires: None,
+            },
+            &[],
+        )
+        .unwrap();
+    }
+
+    fn mint_native_bond(app: &mut App, cw_template_contract: &CwTemplateContract) {
+        approve_mint_fee(app, cw_template_contract);
+        let msg = ExecuteMsg::MintBond {
+            face_value: Uint128::new(FACE_VALUE),
+            interest_rate: INTEREST_RATE,
+            maturity: MATURITY,
+            coupon_frequency: None,
+            day_count: None,
+            call_terms: None,
+            put_terms: None,
+            collateral: None,
+            floating_rate: None,
+        };
+        let user = app.api().addr_make(USER);
+        app.execute_contract(
+            user,
+            cw_template_contract.addr(),
+            &msg,
+            &[Coin::new(REQUIRED_FUNDING, NATIVE_DENOM)],
+        )
+        .unwrap();
+    }
+
+    // Terms of the standard bond, paying its interest at maturity
+    fn standard_terms() -> BondTerms {
+        BondTerms {
+            face_value: Uint128::new(FACE_VALUE),
+            interest_rate: INTEREST_RATE,
+            maturity: MATURITY,
+            coupon_frequency: None,
+            day_count: DayCount::default(),
+            call_terms: None,
+            put_terms: None,
+            collateral: None,
+            floating_rate: None,
+        }
+    }
+
+    // Mint a bond with the given terms from the user with `funds` native coins attached
+    fn mint_bond_with_terms(
+        app: &mut App,
+        cw_template_contract: &CwTemplateContract,
+        terms: BondTerms,
+        funds: u128,
+    ) -> AnyResult<AppResponse> {
+        approve_mint_fee(app, cw_template_contract);
+        let msg = ExecuteMsg::MintBond {
+            face_value: terms.face_value,
+            interest_rate: terms.interest_rate,
+            maturity: terms.maturity,
+            coupon_frequency: terms.coupon_frequency,
+            day_count: Some(terms.day_count),
+            call_terms: terms.call_terms,
+            put_terms: terms.put_terms,
+            collateral: terms.collateral,
+            floating_rate: terms.floating_rate,
+        };
+        execute(app, cw_template_contract, USER, &msg, &[Coin::new(funds, NATIVE_DENOM)])
+    }
+
+    // Redemption price schedule given in permille of face value from each timestamp
+    fn schedule(prices: &[(u64, u64)]) -> Vec<RedemptionPrice> {
+        prices
+            .iter()
+            .map(|&(from, permille)| RedemptionPrice { from, price: Decimal::permille(permille) })
+            .collect()
+    }
+
+    fn seix_balance(app: &App, seix_addr: &Addr, address: &Addr) -> Uint128 {
+        let balance: BalanceResponse = app
+            .wrap()
+            .query_wasm_smart(
+                seix_addr,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+        balance.balance
+    }
+
+    // Give an investor native coins from the user's balance
+    fn fund_investor(app: &mut App, investor: &str, amount: u128) -> Addr {
+        let investor = app.api().addr_make(investor);
+        let user = app.api().addr_make(USER);
+        app.send_tokens(user, investor.clone(), &[Coin::new(amount, NATIVE_DENOM)]).unwrap();
+        investor
+    }
+
+    fn native_balance(app: &App, address: &Addr) -> Uint128 {
+        app.wrap().query_balance(address, NATIVE_DENOM).unwrap().amount
+    }
+
+    // Have the admin set the grace period and penalty rate for bonds not repaid at maturity
+    fn set_default_policy(app: &mut App, cw_template_contract: &CwTemplateContract, grace_period: u64, penalty_rate: Decimal) {
+        let msg = ExecuteMsg::UpdateConfig {
+            admin: None,
+            treasury: None,
+            fee_token: None,
+            fees: None,
+            default_policy: Some(DefaultPolicy { grace_period, penalty_rate }),
+            rate_oracle: None,
+            trusted_tokens: None,
+        };
+        let admin = app.api().addr_make(ADMIN);
+        app.execute_contract(admin, cw_template_contract.addr(), &msg, &[]).unwrap();
+    }
+
+    // Send SeiX to the bond contract with an embedded action
+    fn send_seix(
+        app: &mut App,
+        seix_addr: &Addr,
+        sender: &Addr,
+        cw_template_contract: &CwTemplateContract,
+        amount: u128,
+        msg: &ReceiveMsg,
+    ) -> AnyResult<()> {
+        app.execute_contract(
+            sender.clone(),
+            seix_addr.clone(),
+            &Cw20ExecuteMsg::Send {
+                contract: cw_template_contract.addr().to_string(),
+                amount: Uint128::new(amount),
+                msg: to_json_binary(msg).unwrap(),
+            },
+            &[],
+        )
+        .map(|_| ())
+    }
+
+    // Execute a message on the bond contract from the address made for `sender`
+    fn execute(
+        app: &mut App,
+        cw_template_contract: &CwTemplateContract,
+        sender: &str,
+        msg: &ExecuteMsg,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        let sender = app.api().addr_make(sender);
+        app.execute_contract(sender, cw_template_contract.addr(), msg, funds)
     }
 
     mod bond_tests {
@@ -72,66 +295,2754 @@ mod tests {
 
         #[test]
         fn mint_bond() {
-            let (mut app, cw_template_contract) = proper_instantiate();
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
 
             // Test minting a bond
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            // The mint fee was pulled from the issuer
+            let treasury = app.api().addr_make(PLATFORM_TREASURY);
+            assert_eq!(seix_balance(&app, &seix_addr, &treasury), Uint128::new(10));
+
+            let bond = cw_template_contract
+                .bond::<_, Empty>(&app, 1)
+                .unwrap();
+            assert_eq!(bond.holder, app.api().addr_make(USER));
+            assert_eq!(bond.denom, Denom::Native(NATIVE_DENOM.to_string()));
+            assert_eq!(bond.escrow, Uint128::new(REQUIRED_FUNDING));
+            assert_eq!(
+                app.wrap()
+                    .query_balance(cw_template_contract.addr(), NATIVE_DENOM)
+                    .unwrap()
+                    .amount,
+                Uint128::new(REQUIRED_FUNDING)
+            );
+        }
+
+        #[test]
+        fn mint_bond_requires_full_funding() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+
             let msg = ExecuteMsg::MintBond {
-                face_value: Uint128::new(1000),
-                interest_rate: 5,
-                maturity: 3600,
+                face_value: Uint128::new(FACE_VALUE),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: None,
+                day_count: None,
+                call_terms: None,
+                put_terms: None,
+                collateral: None,
+                floating_rate: None,
+            };
+            let user = app.api().addr_make(USER);
+            let err = app
+                .execute_contract(
+                    user.clone(),
+                    cw_template_contract.addr(),
+                    &msg,
+                    &[Coin::new(FACE_VALUE, NATIVE_DENOM)],
+                )
+                .unwrap_err();
+            assert!(err.root_cause().to_string().contains("110000"));
+
+            // Minting without any funds is rejected as well
+            app.execute_contract(user, cw_template_contract.addr(), &msg, &[])
+                .unwrap_err();
+        }
+
+        #[test]
+        fn mint_bond_with_cw20() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+
+            // The SeiX sent covers the mint fee and the principal
+            let admin = app.api().addr_make(ADMIN);
+            let msg = ReceiveMsg::MintBond {
+                face_value: Uint128::new(FACE_VALUE),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: None,
+                day_count: None,
+                call_terms: None,
+                put_terms: None,
+                collateral: None,
+                floating_rate: None,
             };
-            let cosmos_msg = cw_template_contract.call(msg).unwrap();
-            app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+            send_seix(&mut app, &seix_addr, &admin, &cw_template_contract, REQUIRED_FUNDING + 10, &msg)
+                .unwrap();
 
-            // You could query the bond state and verify the bond was correctly minted
+            let bond = cw_template_contract
+                .bond::<_, Empty>(&app, 1)
+                .unwrap();
+            assert_eq!(bond.issuer, admin);
+            assert_eq!(bond.denom, Denom::Cw20(seix_addr.clone()));
+            assert_eq!(bond.escrow, Uint128::new(REQUIRED_FUNDING));
+            let treasury = app.api().addr_make(PLATFORM_TREASURY);
+            assert_eq!(seix_balance(&app, &seix_addr, &treasury), Uint128::new(10));
         }
 
         #[test]
         fn transfer_bond() {
-            let (mut app, cw_template_contract) = proper_instantiate();
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
 
             // Mint a bond first
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            // Test transferring the bond, paying the fee with a SeiX Send
+            let transfer_msg = ReceiveMsg::TransferBond {
+                bond_id: 1,
+                new_holder: app.api().addr_make(PLATFORM_TREASURY).to_string(),
+            };
+            let user = app.api().addr_make(USER);
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 5, &transfer_msg).unwrap();
+
+            let bond = cw_template_contract
+                .bond::<_, Empty>(&app, 1)
+                .unwrap();
+            assert_eq!(bond.holder, app.api().addr_make(PLATFORM_TREASURY));
+        }
+
+        #[test]
+        fn operations_on_unknown_bond() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+
+            let claim_msg = ExecuteMsg::ClaimCoupon {
+                bond_id: 7,
+            };
+            let user = app.api().addr_make(USER);
+            let err = app
+                .execute_contract(user, cw_template_contract.addr(), &claim_msg, &[])
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::BondNotFound { bond_id: 7 }
+            );
+        }
+
+        #[test]
+        fn payment_errors_carry_their_code() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            approve_mint_fee(&mut app, &cw_template_contract);
+
             let mint_msg = ExecuteMsg::MintBond {
-                face_value: Uint128::new(1000),
-                interest_rate: 5,
-                maturity: 3600,
+                face_value: Uint128::new(FACE_VALUE),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: None,
+                day_count: None,
+                call_terms: None,
+                put_terms: None,
+                collateral: None,
+                floating_rate: None,
             };
-            let cosmos_msg = cw_template_contract.call(mint_msg).unwrap();
-            app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+            let err = execute(&mut app, &cw_template_contract, USER, &mint_msg, &[]).unwrap_err();
+            let err = err.downcast::<ContractError>().unwrap();
+            assert_eq!(err.code(), "PAYMENT");
+            assert!(err.to_string().starts_with("PAYMENT: "));
+        }
 
-            // Test transferring the bond
-            let transfer_msg = ExecuteMsg::TransferBond {
-                bond_id: "1".to_string(),
-                new_holder: Addr::unchecked(PLATFORM_TREASURY),
+        #[test]
+        fn transfer_bond_rejects_invalid_holder() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            // A mistyped address must not receive the bond
+            let transfer_msg = ReceiveMsg::TransferBond {
+                bond_id: 1,
+                new_holder: "not an address".to_string(),
             };
-            let cosmos_msg = cw_template_contract.call(transfer_msg).unwrap();
-            app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+            let user = app.api().addr_make(USER);
+            let err = send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 5, &transfer_msg)
+                .unwrap_err();
+            assert!(err.root_cause().to_string().contains("`new_holder`"));
+
+            let bond = cw_template_contract
+                .bond::<_, Empty>(&app, 1)
+                .unwrap();
+            assert_eq!(bond.holder, user);
         }
 
         #[test]
         fn redeem_bond() {
-            let (mut app, cw_template_contract) = proper_instantiate();
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
 
             // Mint a bond first
-            let mint_msg = ExecuteMsg::MintBond {
-                face_value: Uint128::new(1000),
-                interest_rate: 5,
-                maturity: 3600,
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            // Redeeming before maturity fails, reporting when the bond matures
+            let redeem_msg = ReceiveMsg::RedeemBond {
+                bond_id: 1,
             };
-            let cosmos_msg = cw_template_contract.call(mint_msg).unwrap();
-            app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+            let user = app.api().addr_make(USER);
+            let err = send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 15, &redeem_msg)
+                .unwrap_err();
+            let matures_at = app.block_info().time.seconds() + MATURITY;
+            let err = err.downcast::<ContractError>().unwrap();
+            assert_eq!(err, ContractError::NotMatured { matures_at });
+            assert_eq!(err.code(), "NOT_MATURED");
+            assert!(err.to_string().starts_with("NOT_MATURED: "));
 
             // Fast-forward time to simulate bond maturity
             app.update_block(|block| {
-                block.time = block.time.plus_seconds(3601); // Simulate 3600 seconds
+                block.time = block.time.plus_seconds(MATURITY + 1);
             });
 
             // Test redeeming the bond
-            let redeem_msg = ExecuteMsg::RedeemBond {
-                bond_id: "1".to_string(),
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 15, &redeem_msg).unwrap();
+
+            // The holder received face value plus interest and the escrow is empty
+            assert_eq!(
+                app.wrap().query_balance(&user, NATIVE_DENOM).unwrap().amount,
+                Uint128::new(1_000_000)
+            );
+            assert_eq!(
+                app.wrap()
+                    .query_balance(cw_template_contract.addr(), NATIVE_DENOM)
+                    .unwrap()
+                    .amount,
+                Uint128::zero()
+            );
+
+            // Mint and redeem fees were paid by the user and forwarded to the treasury
+            assert_eq!(
+                seix_balance(&app, &seix_addr, &user),
+                Uint128::new(1_000_000 - 10 - 15)
+            );
+            assert_eq!(
+                seix_balance(&app, &seix_addr, &cw_template_contract.addr()),
+                Uint128::zero()
+            );
+            let treasury = app.api().addr_make(PLATFORM_TREASURY);
+            assert_eq!(seix_balance(&app, &seix_addr, &treasury), Uint128::new(10 + 15));
+        }
+    }
+
+    mod coupon_tests {
+        use super::*;
+
+        // Mint the standard bond paying quarterly coupons of 2_500
+        fn mint_coupon_bond(app: &mut App, cw_template_contract: &CwTemplateContract) -> u64 {
+            approve_mint_fee(app, cw_template_contract);
+            let msg = ExecuteMsg::MintBond {
+                face_value: Uint128::new(FACE_VALUE),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: Some(MATURITY / 4),
+                day_count: None,
+                call_terms: None,
+                put_terms: None,
+                collateral: None,
+                floating_rate: None,
+            };
+            let user = app.api().addr_make(USER);
+            app.execute_contract(
+                user,
+                cw_template_contract.addr(),
+                &msg,
+                &[Coin::new(REQUIRED_FUNDING, NATIVE_DENOM)],
+            )
+            .unwrap();
+            app.block_info().time.seconds()
+        }
+
+        fn next_coupon(app: &App, cw_template_contract: &CwTemplateContract) -> NextCouponResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::NextCoupon {
+                        bond_id: 1,
+                    },
+                )
+                .unwrap()
+        }
+
+        #[test]
+        fn claim_due_coupons_once() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            let issued_at = mint_coupon_bond(&mut app, &cw_template_contract);
+
+            // Nothing is due before the first period ends
+            let next = next_coupon(&app, &cw_template_contract);
+            assert_eq!(next.next_coupon_date, Some(issued_at + MATURITY / 4));
+            assert_eq!(next.amount_due, Uint128::zero());
+            let claim_msg = ExecuteMsg::ClaimCoupon {
+                bond_id: 1,
+            };
+            let user = app.api().addr_make(USER);
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &claim_msg, &[])
+                .unwrap_err();
+
+            // After half a year two coupons are due
+            app.update_block(|block| {
+                block.time = block.time.plus_seconds(MATURITY / 2);
+            });
+            let next = next_coupon(&app, &cw_template_contract);
+            assert_eq!(next.coupons_due, 2);
+            assert_eq!(next.amount_due, Uint128::new(5_000));
+
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &claim_msg, &[])
+                .unwrap();
+            assert_eq!(
+                app.wrap().query_balance(&user, NATIVE_DENOM).unwrap().amount,
+                Uint128::new(1_000_000 - REQUIRED_FUNDING + 5_000)
+            );
+
+            // The same coupons cannot be claimed twice
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &claim_msg, &[])
+                .unwrap_err();
+            let next = next_coupon(&app, &cw_template_contract);
+            assert_eq!(next.next_coupon_date, Some(issued_at + 3 * MATURITY / 4));
+            assert_eq!(next.amount_due, Uint128::zero());
+        }
+
+        #[test]
+        fn coupons_follow_the_holder() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            mint_coupon_bond(&mut app, &cw_template_contract);
+
+            let user = app.api().addr_make(USER);
+            let new_holder = app.api().addr_make("new_holder");
+            let config: ConfigResponse = app
+                .wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::Config {})
+                .unwrap();
+            app.execute_contract(
+                user.clone(),
+                config.fee_token,
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: cw_template_contract.addr().to_string(),
+                    amount: config.fees.transfer,
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+            let transfer_msg = ExecuteMsg::TransferBond {
+                bond_id: 1,
+                new_holder: new_holder.to_string(),
+            };
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &transfer_msg, &[])
+                .unwrap();
+
+            // Anyone can trigger the payment, it always goes to the holder
+            app.update_block(|block| {
+                block.time = block.time.plus_seconds(MATURITY / 4);
+            });
+            let claim_msg = ExecuteMsg::ClaimCoupon {
+                bond_id: 1,
+            };
+            app.execute_contract(user, cw_template_contract.addr(), &claim_msg, &[])
+                .unwrap();
+            assert_eq!(
+                app.wrap().query_balance(&new_holder, NATIVE_DENOM).unwrap().amount,
+                Uint128::new(2_500)
+            );
+        }
+
+        #[test]
+        fn redeem_pays_unclaimed_coupons() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_coupon_bond(&mut app, &cw_template_contract);
+
+            let user = app.api().addr_make(USER);
+            app.update_block(|block| {
+                block.time = block.time.plus_seconds(MATURITY / 4);
+            });
+            let claim_msg = ExecuteMsg::ClaimCoupon {
+                bond_id: 1,
+            };
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &claim_msg, &[])
+                .unwrap();
+
+            // At maturity the remaining three coupons are paid with the face value
+            app.update_block(|block| {
+                block.time = block.time.plus_seconds(MATURITY);
+            });
+            let next = next_coupon(&app, &cw_template_contract);
+            assert_eq!(next.coupons_due, 3);
+            let redeem_msg = ReceiveMsg::RedeemBond {
+                bond_id: 1,
+            };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 15, &redeem_msg).unwrap();
+            assert_eq!(
+                app.wrap().query_balance(&user, NATIVE_DENOM).unwrap().amount,
+                Uint128::new(1_000_000)
+            );
+        }
+    }
+
+    mod interest_tests {
+        use super::*;
+        use crate::msg::{AccruedInterestResponse, RoundingPolicy};
+        use crate::state::DayCount;
+        use cosmwasm_std::Timestamp;
+
+        fn mint_bond_with(
+            app: &mut App,
+            cw_template_contract: &CwTemplateContract,
+            maturity: u64,
+            day_count: DayCount,
+            funding: u128,
+        ) -> AnyResult<()> {
+            approve_mint_fee(app, cw_template_contract);
+            let msg = ExecuteMsg::MintBond {
+                face_value: Uint128::new(FACE_VALUE),
+                interest_rate: INTEREST_RATE,
+                maturity,
+                coupon_frequency: None,
+                day_count: Some(day_count),
+                call_terms: None,
+                put_terms: None,
+                collateral: None,
+                floating_rate: None,
+            };
+            let user = app.api().addr_make(USER);
+            app.execute_contract(
+                user,
+                cw_template_contract.addr(),
+                &msg,
+                &[Coin::new(funding, NATIVE_DENOM)],
+            )
+            .map(|_| ())
+        }
+
+        fn accrued_interest(
+            app: &App,
+            cw_template_contract: &CwTemplateContract,
+            at_time: Option<u64>,
+        ) -> AccruedInterestResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::AccruedInterest {
+                        bond_id: 1,
+                        at_time,
+                    },
+                )
+                .unwrap()
+        }
+
+        #[test]
+        fn accrued_interest_and_redemption_amount() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+            let issued_at = app.block_info().time.seconds();
+
+            // Half way through the year half of the interest has accrued
+            app.update_block(|block| {
+                block.time = block.time.plus_seconds(MATURITY / 2);
+            });
+            let accrued = accrued_interest(&app, &cw_template_contract, None);
+            assert_eq!(accrued.at_time, issued_at + MATURITY / 2);
+            assert_eq!(accrued.day_count, DayCount::Act365);
+            assert_eq!(accrued.accrued_interest, Uint128::new(5_000));
+            assert_eq!(accrued.redemption_amount, Uint128::new(105_000));
+            assert_eq!(accrued.rounding, RoundingPolicy::Floor);
+
+            // Interest stops accruing at maturity
+            let accrued = accrued_interest(&app, &cw_template_contract, Some(issued_at + 2 * MATURITY));
+            assert_eq!(accrued.accrued_interest, Uint128::new(10_000));
+            assert_eq!(accrued.redemption_amount, Uint128::new(REQUIRED_FUNDING));
+        }
+
+        #[test]
+        fn act_360_accrues_over_a_shorter_year() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+
+            // 10% over 365/360 of a year is 10_138.88, rounded down
+            mint_bond_with(&mut app, &cw_template_contract, MATURITY, DayCount::Act360, REQUIRED_FUNDING)
+                .unwrap_err();
+            mint_bond_with(&mut app, &cw_template_contract, MATURITY, DayCount::Act360, 110_138)
+                .unwrap();
+
+            let accrued = accrued_interest(&app, &cw_template_contract, Some(u64::MAX));
+            assert_eq!(accrued.accrued_interest, Uint128::new(10_138));
+        }
+
+        #[test]
+        fn thirty_360_counts_calendar_months() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+
+            // Issued on 2024-01-31 and maturing 30 days later on 2024-03-01
+            let issued_at = 1_706_659_200;
+            app.update_block(|block| {
+                block.time = Timestamp::from_seconds(issued_at);
+            });
+            let maturity = 30 * 24 * 60 * 60;
+
+            // 30/360 counts 31 days: 100_000 * 10% * 31 / 360 = 861.11
+            mint_bond_with(&mut app, &cw_template_contract, maturity, DayCount::Thirty360, 100_861)
+                .unwrap();
+
+            // On 2024-02-29 it counts 29 days: 805.55
+            let accrued = accrued_interest(&app, &cw_template_contract, Some(issued_at + maturity - 24 * 60 * 60));
+            assert_eq!(accrued.accrued_interest, Uint128::new(805));
+            let accrued = accrued_interest(&app, &cw_template_contract, Some(issued_at + maturity));
+            assert_eq!(accrued.redemption_amount, Uint128::new(100_861));
+        }
+    }
+
+    mod nft_tests {
+        use super::*;
+        use crate::msg::BondMetadata;
+        use cw721::msg::{NftInfoResponse, OwnerOfResponse, TokensResponse};
+        use cw721::receiver::ReceiverExecuteMsg;
+
+        // A contract accepting any bond NFT sent to it
+        fn contract_receiver() -> Box<dyn Contract<Empty>> {
+            let contract = ContractWrapper::new(
+                |_, _, _, _: ReceiverExecuteMsg| Ok::<_, StdError>(Response::new()),
+                |_, _, _, _: Empty| Ok::<_, StdError>(Response::new()),
+                |_, _, _: Empty| Err::<cosmwasm_std::Binary, _>(StdError::generic_err("no queries")),
+            );
+            Box::new(contract)
+        }
+
+        // Allow `owner` to pay the transfer fee for the given number of transfers
+        fn approve_transfer_fees(
+            app: &mut App,
+            seix_addr: &Addr,
+            owner: &Addr,
+            cw_template_contract: &CwTemplateContract,
+            transfers: u128,
+        ) {
+            app.execute_contract(
+                owner.clone(),
+                seix_addr.clone(),
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: cw_template_contract.addr().to_string(),
+                    amount: Uint128::new(5 * transfers),
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+        }
+
+        #[test]
+        fn owner_of_and_nft_info() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            let owner: OwnerOfResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::OwnerOf {
+                        token_id: "1".to_string(),
+                        include_expired: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(owner.owner, app.api().addr_make(USER).to_string());
+            assert!(owner.approvals.is_empty());
+
+            let info: NftInfoResponse<BondMetadata> = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::NftInfo {
+                        token_id: "1".to_string(),
+                    },
+                )
+                .unwrap();
+            assert_eq!(info.extension.face_value, Uint128::new(FACE_VALUE));
+            assert_eq!(info.extension.interest_rate, INTEREST_RATE);
+            assert_eq!(info.extension.maturity, MATURITY);
+        }
+
+        #[test]
+        fn approved_spender_can_transfer() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            let user = app.api().addr_make(USER);
+            let spender = app.api().addr_make(ADMIN);
+            let recipient = app.api().addr_make("recipient");
+            approve_transfer_fees(&mut app, &seix_addr, &spender, &cw_template_contract, 1);
+
+            let transfer_msg = ExecuteMsg::TransferNft {
+                recipient: recipient.to_string(),
+                token_id: "1".to_string(),
+            };
+            app.execute_contract(spender.clone(), cw_template_contract.addr(), &transfer_msg, &[])
+                .unwrap_err();
+
+            let approve_msg = ExecuteMsg::Approve {
+                spender: spender.to_string(),
+                token_id: "1".to_string(),
+                expires: None,
+            };
+            app.execute_contract(user, cw_template_contract.addr(), &approve_msg, &[])
+                .unwrap();
+            app.execute_contract(spender.clone(), cw_template_contract.addr(), &transfer_msg, &[])
+                .unwrap();
+
+            // The new holder starts without approvals
+            let bond = cw_template_contract
+                .bond::<_, Empty>(&app, 1)
+                .unwrap();
+            assert_eq!(bond.holder, recipient);
+            assert!(bond.approvals.is_empty());
+        }
+
+        #[test]
+        fn revoked_spender_cannot_transfer() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            let user = app.api().addr_make(USER);
+            let spender = app.api().addr_make(ADMIN);
+            approve_transfer_fees(&mut app, &seix_addr, &spender, &cw_template_contract, 1);
+
+            let approve_msg = ExecuteMsg::Approve {
+                spender: spender.to_string(),
+                token_id: "1".to_string(),
+                expires: None,
+            };
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &approve_msg, &[])
+                .unwrap();
+            let revoke_msg = ExecuteMsg::Revoke {
+                spender: spender.to_string(),
+                token_id: "1".to_string(),
+            };
+            app.execute_contract(user, cw_template_contract.addr(), &revoke_msg, &[])
+                .unwrap();
+
+            let transfer_msg = ExecuteMsg::TransferNft {
+                recipient: spender.to_string(),
+                token_id: "1".to_string(),
+            };
+            app.execute_contract(spender, cw_template_contract.addr(), &transfer_msg, &[])
+                .unwrap_err();
+        }
+
+        #[test]
+        fn send_nft_to_contract() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            let receiver_id = app.store_code(contract_receiver());
+            let user = app.api().addr_make(USER);
+            let receiver = app
+                .instantiate_contract(receiver_id, user.clone(), &Empty {}, &[], "receiver", None)
+                .unwrap();
+            approve_transfer_fees(&mut app, &seix_addr, &user, &cw_template_contract, 1);
+
+            let send_msg = ExecuteMsg::SendNft {
+                contract: receiver.to_string(),
+                token_id: "1".to_string(),
+                msg: to_json_binary("hello").unwrap(),
+            };
+            app.execute_contract(user, cw_template_contract.addr(), &send_msg, &[])
+                .unwrap();
+
+            let bond = cw_template_contract
+                .bond::<_, Empty>(&app, 1)
+                .unwrap();
+            assert_eq!(bond.holder, receiver);
+        }
+
+        #[test]
+        fn list_tokens() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            for _ in 0..3 {
+                mint_native_bond(&mut app, &cw_template_contract);
+            }
+
+            // Hand the second bond to another holder
+            let user = app.api().addr_make(USER);
+            let other = app.api().addr_make("other");
+            approve_transfer_fees(&mut app, &seix_addr, &user, &cw_template_contract, 1);
+            let transfer_msg = ExecuteMsg::TransferNft {
+                recipient: other.to_string(),
+                token_id: "2".to_string(),
+            };
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &transfer_msg, &[])
+                .unwrap();
+
+            let all: TokensResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::AllTokens {
+                        start_after: Some("1".to_string()),
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(all.tokens, vec!["2".to_string(), "3".to_string()]);
+
+            let owned: TokensResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::Tokens {
+                        owner: user.to_string(),
+                        start_after: None,
+                        limit: Some(10),
+                    },
+                )
+                .unwrap();
+            assert_eq!(owned.tokens, vec!["1".to_string(), "3".to_string()]);
+        }
+    }
+
+    mod listing_tests {
+        use super::*;
+        use crate::contract::interest_due;
+        use crate::state::DayCount;
+        use crate::msg::BondsResponse;
+
+        // Mint a non-coupon bond from the user with the given maturity period
+        fn mint_bond_maturing(app: &mut App, cw_template_contract: &CwTemplateContract, maturity: u64) {
+            approve_mint_fee(app, cw_template_contract);
+            let face_value = Uint128::new(FACE_VALUE);
+            let funding = face_value + interest_due(face_value, INTEREST_RATE, DayCount::Act365, 0, maturity);
+            let msg = ExecuteMsg::MintBond {
+                face_value,
+                interest_rate: INTEREST_RATE,
+                maturity,
+                coupon_frequency: None,
+                day_count: None,
+                call_terms: None,
+                put_terms: None,
+                collateral: None,
+                floating_rate: None,
             };
-            let cosmos_msg = cw_template_contract.call(redeem_msg).unwrap();
-            app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+            let user = app.api().addr_make(USER);
+            app.execute_contract(
+                user,
+                cw_template_contract.addr(),
+                &msg,
+                &[Coin::new(funding, NATIVE_DENOM)],
+            )
+            .unwrap();
+        }
+
+        fn bond_ids(response: BondsResponse) -> Vec<u64> {
+            response.bonds.into_iter().map(|info| info.bond_id).collect()
+        }
+
+        #[test]
+        fn list_bonds_paginated() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            for _ in 0..3 {
+                mint_native_bond(&mut app, &cw_template_contract);
+            }
+
+            let page: BondsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::ListBonds {
+                        start_after: None,
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+            assert_eq!(page.bonds[0].bond.face_value, Uint128::new(FACE_VALUE));
+            assert_eq!(bond_ids(page), vec![1, 2]);
+
+            let page: BondsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::ListBonds {
+                        start_after: Some(2),
+                        limit: Some(2),
+                    },
+                )
+                .unwrap();
+            assert_eq!(bond_ids(page), vec![3]);
+        }
+
+        #[test]
+        fn bonds_by_holder_and_issuer() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            // The admin issues a third bond and the user hands the first one over
+            let admin = app.api().addr_make(ADMIN);
+            let msg = ReceiveMsg::MintBond {
+                face_value: Uint128::new(FACE_VALUE),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: None,
+                day_count: None,
+                call_terms: None,
+                put_terms: None,
+                collateral: None,
+                floating_rate: None,
+            };
+            send_seix(&mut app, &seix_addr, &admin, &cw_template_contract, REQUIRED_FUNDING + 10, &msg)
+                .unwrap();
+            let user = app.api().addr_make(USER);
+            let transfer_msg = ReceiveMsg::TransferBond {
+                bond_id: 1,
+                new_holder: admin.to_string(),
+            };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 5, &transfer_msg).unwrap();
+
+            let held: BondsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::BondsByHolder {
+                        holder: admin.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(bond_ids(held), vec![1, 3]);
+
+            let issued: BondsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::BondsByIssuer {
+                        issuer: user.to_string(),
+                        start_after: Some(1),
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(bond_ids(issued), vec![2]);
+        }
+
+        #[test]
+        fn bonds_maturing_between() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            let issued_at = app.block_info().time.seconds();
+            mint_bond_maturing(&mut app, &cw_template_contract, 300);
+            mint_bond_maturing(&mut app, &cw_template_contract, 100);
+            mint_bond_maturing(&mut app, &cw_template_contract, 200);
+            mint_bond_maturing(&mut app, &cw_template_contract, 200);
+
+            // Results are ordered by maturity and bounded on both ends
+            let query = |start_after: Option<u64>| -> BondsResponse {
+                app.wrap()
+                    .query_wasm_smart(
+                        cw_template_contract.addr(),
+                        &QueryMsg::BondsMaturingBetween {
+                            from: issued_at + 100,
+                            to: issued_at + 200,
+                            start_after,
+                            limit: Some(2),
+                        },
+                    )
+                    .unwrap()
+            };
+            assert_eq!(bond_ids(query(None)), vec![2, 3]);
+            assert_eq!(bond_ids(query(Some(3))), vec![4]);
+        }
+    }
+
+    mod migration_tests {
+        use super::*;
+        use crate::msg::{BondsResponse, LegacyConfig, MigrateMsg};
+        use crate::state::{BOND_COUNT, CONFIG, LEGACY_BONDS, LEGACY_SEIX_CONTRACT};
+
+        // Replace the storage of the contract with what version 0.1.0 wrote: no config, the
+        // SeiX contract on its own, and bonds under the given string keys with a whole
+        // percent rate and none of the fields added since
+        fn store_legacy_state(
+            app: &mut App,
+            cw_template_contract: &CwTemplateContract,
+            seix_addr: &Addr,
+            keys: &[&str],
+        ) {
+            let issuer = app.api().addr_make(USER);
+            let mut storage = app.contract_storage_mut(&cw_template_contract.addr());
+            CONFIG.remove(storage.as_mut());
+            LEGACY_SEIX_CONTRACT.save(storage.as_mut(), seix_addr).unwrap();
+            for key in keys {
+                let json = format!(
+                    r#"{{"issuer":"{issuer}","face_value":"{FACE_VALUE}","interest_rate":10,"maturity":{MATURITY},"holder":"{issuer}","issued_at":1571797419}}"#
+                );
+                storage.set(&LEGACY_BONDS.key(key.to_string()), json.as_bytes());
+            }
+            let bond_count = keys.iter().map(|key| key.parse::<u64>().unwrap()).max().unwrap_or(0);
+            BOND_COUNT.save(storage.as_mut(), &bond_count).unwrap();
+            cw2::set_contract_version(storage.as_mut(), "crates.io:seibond-contract", "0.1.0")
+                .unwrap();
+        }
+
+        // The settings 0.1.0 did not store, as proper_instantiate sets them
+        fn legacy_config(app: &App) -> LegacyConfig {
+            LegacyConfig {
+                admin: app.api().addr_make(ADMIN).to_string(),
+                treasury: app.api().addr_make(PLATFORM_TREASURY).to_string(),
+                fees: FeeSchedule {
+                    mint: Uint128::new(10),
+                    transfer: Uint128::new(5),
+                    redeem: Uint128::new(15),
+                },
+            }
+        }
+
+        fn migrate(app: &mut App, cw_template_contract: &CwTemplateContract, msg: &MigrateMsg) -> AnyResult<()> {
+            let code_id = app
+                .wrap()
+                .query_wasm_contract_info(cw_template_contract.addr())
+                .unwrap()
+                .code_id;
+            let admin = app.api().addr_make(ADMIN);
+            app.migrate_contract(admin, cw_template_contract.addr(), msg, code_id)
+                .map(|_| ())
+        }
+
+        #[test]
+        fn migrate_string_keys_to_u64() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            store_legacy_state(&mut app, &cw_template_contract, &seix_addr, &["10", "2"]);
+
+            let native = Denom::Native(NATIVE_DENOM.to_string());
+            let msg = MigrateMsg {
+                legacy_denom: Some(native.clone()),
+                legacy_config: Some(legacy_config(&app)),
+            };
+            migrate(&mut app, &cw_template_contract, &msg).unwrap();
+
+            // Bonds are ordered numerically and reachable through the indexes again
+            let user = app.api().addr_make(USER);
+            let held: BondsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::BondsByHolder {
+                        holder: user.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            let ids: Vec<u64> = held.bonds.into_iter().map(|info| info.bond_id).collect();
+            assert_eq!(ids, vec![2, 10]);
+
+            // Legacy bonds were never escrowed, they wait for the issuer to fund them
+            let pending: BondsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::BondsByStatus { status: BondStatus::Pending, start_after: None, limit: None },
+                )
+                .unwrap();
+            assert_eq!(pending.bonds.len(), 2);
+            let bond = cw_template_contract
+                .bond::<_, Empty>(&app, 10)
+                .unwrap();
+            assert_eq!(bond.holder, user);
+            assert_eq!(bond.interest_rate, INTEREST_RATE);
+            assert_eq!(bond.denom, native);
+            assert_eq!(bond.escrow, Uint128::zero());
+            assert!(bond.approvals.is_empty());
+
+            let version = cw2::query_contract_info(&app.wrap(), cw_template_contract.addr()).unwrap();
+            assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+
+            // The config is rebuilt around the SeiX contract 0.1.0 stored
+            let config: ConfigResponse = app
+                .wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::Config {})
+                .unwrap();
+            assert_eq!(config.fee_token, seix_addr);
+            assert_eq!(config.admin, app.api().addr_make(ADMIN));
+            assert_eq!(config.treasury, app.api().addr_make(PLATFORM_TREASURY));
+            assert_eq!(config.fees.mint, Uint128::new(10));
+
+            // The issuer can fund a legacy bond and mint new ones after the last legacy ID
+            execute(
+                &mut app,
+                &cw_template_contract,
+                USER,
+                &ExecuteMsg::FundBond { bond_id: 10 },
+                &[Coin::new(REQUIRED_FUNDING, NATIVE_DENOM)],
+            )
+            .unwrap();
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 10).unwrap();
+            assert_eq!(bond.status, BondStatus::Active);
+            mint_native_bond(&mut app, &cw_template_contract);
+            assert_eq!(cw_template_contract.bond::<_, Empty>(&app, 11).unwrap().holder, user);
+        }
+
+        #[test]
+        fn legacy_state_needs_a_config() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            store_legacy_state(&mut app, &cw_template_contract, &seix_addr, &[]);
+
+            let msg = MigrateMsg { legacy_denom: None, legacy_config: None };
+            let err = migrate(&mut app, &cw_template_contract, &msg).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::LegacyConfigRequired {});
+        }
+
+        #[test]
+        fn migrate_percent_rates() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+            {
+                // Bond 1 as 0.2.0 stored it, before rates were a Decimal
+                let user = app.api().addr_make(USER);
+                let json = format!(
+                    r#"{{"issuer":"{user}","face_value":"{FACE_VALUE}","interest_rate":10,"maturity":{MATURITY},"holder":"{user}","issued_at":1571797419,"denom":{{"native":"{NATIVE_DENOM}"}},"escrow":"{REQUIRED_FUNDING}","coupon_frequency":null,"coupons_paid":0,"approvals":[]}}"#
+                );
+                let mut storage = app.contract_storage_mut(&cw_template_contract.addr());
+                storage.set(&crate::state::BONDS.key(1), json.as_bytes());
+                cw2::set_contract_version(storage.as_mut(), "crates.io:seibond-contract", "0.2.0")
+                    .unwrap();
+            }
+
+            migrate(&mut app, &cw_template_contract, &MigrateMsg { legacy_denom: None, legacy_config: None }).unwrap();
+
+            let bond = cw_template_contract
+                .bond::<_, Empty>(&app, 1)
+                .unwrap();
+            assert_eq!(bond.interest_rate, INTEREST_RATE);
+            let storage = app.contract_storage(&cw_template_contract.addr());
+            let stored = storage.get(&crate::state::BONDS.key(1)).unwrap();
+            assert!(String::from_utf8(stored).unwrap().contains(r#""interest_rate":"0.1""#));
+        }
+
+        #[test]
+        fn migrate_status_from_escrow() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+            mint_native_bond(&mut app, &cw_template_contract);
+            {
+                // Before 0.3.0 bonds had no status, bond 2 was sold unfunded by an offering
+                let mut storage = app.contract_storage_mut(&cw_template_contract.addr());
+                for bond_id in [1, 2] {
+                    let mut bond = crate::state::BONDS.load(storage.as_ref(), bond_id).unwrap();
+                    if bond_id == 2 {
+                        bond.escrow = Uint128::new(REQUIRED_FUNDING - 1);
+                    }
+                    let json = String::from_utf8(cosmwasm_std::to_json_vec(&bond).unwrap())
+                        .unwrap()
+                        .replace(r#","status":"active""#, "");
+                    storage.set(&crate::state::BONDS.key(bond_id), json.as_bytes());
+                }
+                cw2::set_contract_version(storage.as_mut(), "crates.io:seibond-contract", "0.2.0")
+                    .unwrap();
+            }
+
+            migrate(&mut app, &cw_template_contract, &MigrateMsg { legacy_denom: None, legacy_config: None }).unwrap();
+
+            let status = |app: &App, bond_id| cw_template_contract.bond::<_, Empty>(app, bond_id).unwrap().status;
+            assert_eq!(status(&app, 1), BondStatus::Active);
+            assert_eq!(status(&app, 2), BondStatus::Pending);
+            let pending: crate::msg::BondsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::BondsByStatus { status: BondStatus::Pending, start_after: None, limit: None },
+                )
+                .unwrap();
+            let ids: Vec<u64> = pending.bonds.into_iter().map(|info| info.bond_id).collect();
+            assert_eq!(ids, vec![2]);
+        }
+
+        #[test]
+        fn legacy_bonds_need_a_denom() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            store_legacy_state(&mut app, &cw_template_contract, &seix_addr, &["1"]);
+
+            let msg = MigrateMsg { legacy_denom: None, legacy_config: Some(legacy_config(&app)) };
+            let err = migrate(&mut app, &cw_template_contract, &msg).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::LegacyDenomRequired {});
+        }
+
+        #[test]
+        fn refuse_downgrade() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            {
+                let mut storage = app.contract_storage_mut(&cw_template_contract.addr());
+                cw2::set_contract_version(storage.as_mut(), "crates.io:seibond-contract", "9.0.0")
+                    .unwrap();
+            }
+            let err = migrate(&mut app, &cw_template_contract, &MigrateMsg { legacy_denom: None, legacy_config: None }).unwrap_err();
+            assert!(err.root_cause().to_string().contains("newer version"));
+        }
+    }
+
+    mod fee_tests {
+        use super::*;
+
+        #[test]
+        fn sent_fee_must_match_schedule() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            let transfer_msg = ReceiveMsg::TransferBond {
+                bond_id: 1,
+                new_holder: app.api().addr_make(PLATFORM_TREASURY).to_string(),
+            };
+            let user = app.api().addr_make(USER);
+            let err = send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 4, &transfer_msg)
+                .unwrap_err();
+            assert!(err.root_cause().to_string().contains("Invalid platform fee"));
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 6, &transfer_msg)
+                .unwrap_err();
+        }
+
+        #[test]
+        fn fee_hook_must_come_from_seix() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            // A hook delivered by any other contract is not a SeiX payment
+            let msg = ExecuteMsg::Receive(cw20::Cw20ReceiveMsg {
+                sender: app.api().addr_make(USER).to_string(),
+                amount: Uint128::new(5),
+                msg: to_json_binary(&ReceiveMsg::TransferBond {
+                    bond_id: 1,
+                    new_holder: app.api().addr_make(PLATFORM_TREASURY).to_string(),
+                })
+                .unwrap(),
+            });
+            let fake_token = app.api().addr_make("fake_token");
+            let err = app
+                .execute_contract(fake_token, cw_template_contract.addr(), &msg, &[])
+                .unwrap_err();
+            assert!(err.root_cause().to_string().contains("SeiX"));
+        }
+
+        #[test]
+        fn spoofed_sender_cannot_spend_allowance() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            approve_mint_fee(&mut app, &cw_template_contract);
+            let user = app.api().addr_make(USER);
+            let fake_token = app.api().addr_make("fake_token");
+
+            // Any contract can deliver a hook claiming the user sent it tokens
+            let spoofed = |msg: &ReceiveMsg| ExecuteMsg::Receive(cw20::Cw20ReceiveMsg {
+                sender: user.to_string(),
+                amount: Uint128::new(REQUIRED_FUNDING),
+                msg: to_json_binary(msg).unwrap(),
+            });
+            let mint = ReceiveMsg::MintBond {
+                face_value: Uint128::new(FACE_VALUE),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: None,
+                day_count: None,
+                call_terms: None,
+                put_terms: None,
+                collateral: None,
+                floating_rate: None,
+            };
+            let err = app
+                .execute_contract(fake_token.clone(), cw_template_contract.addr(), &spoofed(&mint), &[])
+                .unwrap_err();
+            let untrusted = ContractError::UntrustedToken { token: fake_token.to_string() };
+            assert_eq!(err.downcast::<ContractError>().unwrap(), untrusted);
+
+            // Orders in the token cannot be bought with it either
+            mint_native_bond(&mut app, &cw_template_contract);
+            let list = ExecuteMsg::ListBond {
+                bond_id: 1,
+                denom: Denom::Cw20(fake_token.clone()),
+                price: Uint128::new(REQUIRED_FUNDING),
+                expires: None,
+            };
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &list, &[]).unwrap();
+            let buy = ReceiveMsg::BuyBond { order_id: 1 };
+            let err = app
+                .execute_contract(fake_token.clone(), cw_template_contract.addr(), &spoofed(&buy), &[])
+                .unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), untrusted);
+            assert_eq!(seix_balance(&app, &seix_addr, &user), Uint128::new(1_000_000 - 10));
+
+            // Once the admin trusts the token its hooks pull the fee from the sender's allowance
+            let update = ExecuteMsg::UpdateConfig {
+                admin: None,
+                treasury: None,
+                fee_token: None,
+                fees: None,
+                default_policy: None,
+                rate_oracle: None,
+                trusted_tokens: Some(vec![fake_token.to_string()]),
+            };
+            let admin = app.api().addr_make(ADMIN);
+            app.execute_contract(admin, cw_template_contract.addr(), &update, &[]).unwrap();
+            approve_mint_fee(&mut app, &cw_template_contract);
+            app.execute_contract(fake_token.clone(), cw_template_contract.addr(), &spoofed(&mint), &[])
+                .unwrap();
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 2).unwrap();
+            assert_eq!(bond.denom, Denom::Cw20(fake_token));
+            assert_eq!(seix_balance(&app, &seix_addr, &user), Uint128::new(1_000_000 - 20));
+        }
+
+        #[test]
+        fn direct_call_pulls_fee_from_allowance() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            let transfer_msg = ExecuteMsg::TransferBond {
+                bond_id: 1,
+                new_holder: app.api().addr_make(PLATFORM_TREASURY).to_string(),
+            };
+            let user = app.api().addr_make(USER);
+
+            // Without an allowance the fee cannot be collected
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &transfer_msg, &[])
+                .unwrap_err();
+
+            app.execute_contract(
+                user.clone(),
+                seix_addr.clone(),
+                &Cw20ExecuteMsg::IncreaseAllowance {
+                    spender: cw_template_contract.addr().to_string(),
+                    amount: Uint128::new(5),
+                    expires: None,
+                },
+                &[],
+            )
+            .unwrap();
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &transfer_msg, &[])
+                .unwrap();
+            assert_eq!(
+                seix_balance(&app, &seix_addr, &user),
+                Uint128::new(1_000_000 - 10 - 5)
+            );
+        }
+    }
+
+    mod config_tests {
+        use super::*;
+
+        #[test]
+        fn query_config() {
+            let (app, cw_template_contract, seix_addr) = proper_instantiate();
+
+            let config: ConfigResponse = app
+                .wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::Config {})
+                .unwrap();
+            assert_eq!(config.admin, app.api().addr_make(ADMIN));
+            assert_eq!(config.treasury, app.api().addr_make(PLATFORM_TREASURY));
+            assert_eq!(config.fee_token, seix_addr);
+            assert_eq!(config.fees.transfer, Uint128::new(5));
+        }
+
+        #[test]
+        fn update_config() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+
+            let new_treasury = app.api().addr_make("new_treasury");
+            let msg = ExecuteMsg::UpdateConfig {
+                admin: None,
+                treasury: Some(new_treasury.to_string()),
+                fee_token: None,
+                fees: Some(FeeSchedule {
+                    mint: Uint128::new(20),
+                    transfer: Uint128::zero(),
+                    redeem: Uint128::new(30),
+                }),
+                default_policy: None,
+                rate_oracle: None,
+                trusted_tokens: None,
+            };
+
+            // Only the admin can update the config
+            let user = app.api().addr_make(USER);
+            app.execute_contract(user, cw_template_contract.addr(), &msg, &[])
+                .unwrap_err();
+            let admin = app.api().addr_make(ADMIN);
+            app.execute_contract(admin, cw_template_contract.addr(), &msg, &[])
+                .unwrap();
+
+            // New operations are charged with the updated schedule
+            mint_native_bond(&mut app, &cw_template_contract);
+            assert_eq!(seix_balance(&app, &seix_addr, &new_treasury), Uint128::new(20));
+        }
+    }
+
+    mod offering_tests {
+        use super::*;
+        use crate::msg::{OfferingResponse, SubscriptionResponse};
+        use crate::state::OfferingStatus;
+
+        const OFFERING_PERIOD: u64 = 7 * 24 * 60 * 60;
+        const INVESTOR_A: &str = "investor_a";
+        const INVESTOR_B: &str = "investor_b";
+
+        // Open an offering from the user selling one year bonds at 80% of face value
+        fn open_offering(app: &mut App, cw_template_contract: &CwTemplateContract, min_raise: u128, max_raise: u128) {
+            approve_mint_fee(app, cw_template_contract);
+            let msg = ExecuteMsg::OpenOffering {
+                denom: Denom::Native(NATIVE_DENOM.to_string()),
+                price: Decimal::percent(80),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: None,
+                day_count: None,
+                start: None,
+                end: app.block_info().time.seconds() + OFFERING_PERIOD,
+                min_raise: Uint128::new(min_raise),
+                max_raise: Uint128::new(max_raise),
+            };
+            let user = app.api().addr_make(USER);
+            app.execute_contract(user, cw_template_contract.addr(), &msg, &[]).unwrap();
+        }
+
+        fn subscribe(app: &mut App, cw_template_contract: &CwTemplateContract, investor: &Addr, amount: u128) -> AnyResult<()> {
+            let msg = ExecuteMsg::Subscribe { offering_id: 1 };
+            app.execute_contract(investor.clone(), cw_template_contract.addr(), &msg, &[Coin::new(amount, NATIVE_DENOM)])
+                .map(|_| ())
+        }
+
+        fn close(app: &mut App, cw_template_contract: &CwTemplateContract) -> AnyResult<()> {
+            close_page(app, cw_template_contract, None)
+        }
+
+        fn close_page(app: &mut App, cw_template_contract: &CwTemplateContract, limit: Option<u32>) -> AnyResult<()> {
+            let msg = ExecuteMsg::CloseOffering { offering_id: 1, limit };
+            let admin = app.api().addr_make(ADMIN);
+            app.execute_contract(admin, cw_template_contract.addr(), &msg, &[]).map(|_| ())
+        }
+
+        fn offering(app: &App, cw_template_contract: &CwTemplateContract) -> OfferingResponse {
+            app.wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::Offering { offering_id: 1 })
+                .unwrap()
+        }
+
+        #[test]
+        fn oversubscribed_offering_allocates_pro_rata() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            open_offering(&mut app, &cw_template_contract, 10_000, 40_000);
+
+            let investor_a = fund_investor(&mut app, INVESTOR_A, 100_000);
+            let investor_b = fund_investor(&mut app, INVESTOR_B, 100_000);
+            subscribe(&mut app, &cw_template_contract, &investor_a, 20_000).unwrap();
+            subscribe(&mut app, &cw_template_contract, &investor_a, 10_000).unwrap();
+            subscribe(&mut app, &cw_template_contract, &investor_b, 20_000).unwrap();
+
+            let subscription: SubscriptionResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::Subscription { offering_id: 1, investor: investor_a.to_string() },
+                )
+                .unwrap();
+            assert_eq!(subscription.amount, Uint128::new(30_000));
+
+            // The offering cannot close while subscriptions are open
+            let err = close(&mut app, &cw_template_contract).unwrap_err();
+            let ends_at = offering(&app, &cw_template_contract).end;
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::OfferingNotEnded { ends_at });
+
+            app.update_block(|block| block.time = block.time.plus_seconds(OFFERING_PERIOD));
+            let user = app.api().addr_make(USER);
+            let issuer_balance = native_balance(&app, &user);
+            close(&mut app, &cw_template_contract).unwrap();
+
+            // 40_000 of the 50_000 subscribed is accepted, 80% of every subscription
+            let offering = offering(&app, &cw_template_contract);
+            assert_eq!(offering.status, OfferingStatus::Settled);
+            assert_eq!(offering.raised, Uint128::new(40_000));
+            assert_eq!(native_balance(&app, &user), issuer_balance + Uint128::new(40_000));
+            assert_eq!(native_balance(&app, &investor_a), Uint128::new(100_000 - 24_000));
+            assert_eq!(native_balance(&app, &investor_b), Uint128::new(100_000 - 16_000));
+
+            // Each investor holds a bond worth their accepted amount at the offering price
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 1).unwrap();
+            assert_eq!((bond.holder, bond.face_value), (investor_a, Uint128::new(30_000)));
+            assert_eq!(bond.issuer, user);
+            assert_eq!(bond.escrow, Uint128::zero());
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 2).unwrap();
+            assert_eq!((bond.holder, bond.face_value), (investor_b, Uint128::new(20_000)));
+
+            // An offering is only closed once
+            let err = close(&mut app, &cw_template_contract).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::OfferingNotOpen { offering_id: 1 });
+        }
+
+        #[test]
+        fn offering_below_minimum_is_refunded() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            open_offering(&mut app, &cw_template_contract, 10_000, 40_000);
+
+            let investor = fund_investor(&mut app, INVESTOR_A, 100_000);
+            subscribe(&mut app, &cw_template_contract, &investor, 5_000).unwrap();
+            assert_eq!(native_balance(&app, &investor), Uint128::new(95_000));
+
+            // Subscriptions close with the period
+            app.update_block(|block| block.time = block.time.plus_seconds(OFFERING_PERIOD));
+            let err = subscribe(&mut app, &cw_template_contract, &investor, 5_000).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "OUTSIDE_SUBSCRIPTION_PERIOD");
+
+            close(&mut app, &cw_template_contract).unwrap();
+            let offering = offering(&app, &cw_template_contract);
+            assert_eq!(offering.status, OfferingStatus::Refunded);
+            assert_eq!(offering.raised, Uint128::zero());
+            assert_eq!(native_balance(&app, &investor), Uint128::new(100_000));
+            assert!(cw_template_contract.bond::<_, Empty>(&app, 1).is_err());
+        }
+
+        #[test]
+        fn offering_closes_in_pages() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            open_offering(&mut app, &cw_template_contract, 10_000, 40_000);
+
+            let investors: Vec<Addr> = [INVESTOR_A, INVESTOR_B, "investor_c"]
+                .into_iter()
+                .map(|investor| fund_investor(&mut app, investor, 100_000))
+                .collect();
+            for investor in &investors {
+                subscribe(&mut app, &cw_template_contract, investor, 20_000).unwrap();
+            }
+            app.update_block(|block| block.time = block.time.plus_seconds(OFFERING_PERIOD));
+            let user = app.api().addr_make(USER);
+            let issuer_balance = native_balance(&app, &user);
+
+            // The first page settles two of the three subscriptions
+            let closed_at = app.block_info().time.seconds();
+            close_page(&mut app, &cw_template_contract, Some(2)).unwrap();
+            let closing = offering(&app, &cw_template_contract);
+            assert_eq!(closing.status, OfferingStatus::Closing);
+            assert!(cw_template_contract.bond::<_, Empty>(&app, 2).is_ok());
+            assert!(cw_template_contract.bond::<_, Empty>(&app, 3).is_err());
+            let err = subscribe(&mut app, &cw_template_contract, &investors[0], 1_000).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::OfferingNotOpen { offering_id: 1 });
+
+            // The next one settles the rest an hour later, 40_000 of the 60_000 subscribed is
+            // accepted and every bond is issued when closing started
+            app.update_block(|block| block.time = block.time.plus_seconds(60 * 60));
+            close_page(&mut app, &cw_template_contract, Some(2)).unwrap();
+            let settled = offering(&app, &cw_template_contract);
+            assert_eq!(settled.status, OfferingStatus::Settled);
+            assert_eq!(settled.raised, Uint128::new(39_999));
+            assert_eq!(native_balance(&app, &user), issuer_balance + Uint128::new(39_999));
+            for (index, investor) in investors.iter().enumerate() {
+                assert_eq!(native_balance(&app, investor), Uint128::new(100_000 - 13_333));
+                let bond = cw_template_contract.bond::<_, Empty>(&app, index as u64 + 1).unwrap();
+                assert_eq!(bond.holder, *investor);
+                assert_eq!(bond.issued_at, closed_at);
+            }
+            let err = close(&mut app, &cw_template_contract).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::OfferingNotOpen { offering_id: 1 });
+        }
+
+        #[test]
+        fn issuer_repays_offered_bond() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            open_offering(&mut app, &cw_template_contract, 0, 80_000);
+            let investor = fund_investor(&mut app, INVESTOR_A, 80_000);
+            subscribe(&mut app, &cw_template_contract, &investor, 80_000).unwrap();
+            app.update_block(|block| block.time = block.time.plus_seconds(OFFERING_PERIOD));
+            close(&mut app, &cw_template_contract).unwrap();
+
+            // The investor pays the redeem fee in SeiX
+            let admin = app.api().addr_make(ADMIN);
+            let msg = Cw20ExecuteMsg::Transfer { recipient: investor.to_string(), amount: Uint128::new(15) };
+            app.execute_contract(admin, seix_addr.clone(), &msg, &[]).unwrap();
+
+            // Nothing is escrowed until the issuer repays the bond
+            app.update_block(|block| block.time = block.time.plus_seconds(MATURITY));
+            let redeem_msg = ReceiveMsg::RedeemBond { bond_id: 1 };
+            let err = send_seix(&mut app, &seix_addr, &investor, &cw_template_contract, 15, &redeem_msg)
+                .unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "INSUFFICIENT_FUNDS");
+
+            // A face value of 100_000 at 10% is owed, and not a unit more
+            let user = app.api().addr_make(USER);
+            let fund = |amount| (ExecuteMsg::FundBond { bond_id: 1 }, vec![Coin::new(amount, NATIVE_DENOM)]);
+            let (msg, funds) = fund(REQUIRED_FUNDING + 1);
+            let err = app
+                .execute_contract(user.clone(), cw_template_contract.addr(), &msg, &funds)
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::Overfunded {
+                    outstanding: Uint128::new(REQUIRED_FUNDING),
+                    received: Uint128::new(REQUIRED_FUNDING + 1),
+                }
+            );
+            let (msg, funds) = fund(REQUIRED_FUNDING);
+            app.execute_contract(user, cw_template_contract.addr(), &msg, &funds).unwrap();
+
+            send_seix(&mut app, &seix_addr, &investor, &cw_template_contract, 15, &redeem_msg).unwrap();
+            assert_eq!(native_balance(&app, &investor), Uint128::new(REQUIRED_FUNDING));
+        }
+    }
+
+    mod auction_tests {
+        use super::*;
+        use crate::msg::{AuctionPriceResponse, AuctionResponse};
+        use crate::state::{AuctionStatus, PriceDecay};
+
+        const WINDOW: u64 = 1_000;
+        const BIDDER_A: &str = "bidder_a";
+        const BIDDER_B: &str = "bidder_b";
+
+        // Open an auction from the user of 10 units of 1_000 face value, priced from 1_000 down to 500
+        fn open_auction(app: &mut App, cw_template_contract: &CwTemplateContract, decay: PriceDecay) {
+            approve_mint_fee(app, cw_template_contract);
+            let msg = ExecuteMsg::OpenAuction {
+                denom: Denom::Native(NATIVE_DENOM.to_string()),
+                face_value: Uint128::new(1_000),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: None,
+                day_count: None,
+                supply: 10,
+                start_price: Uint128::new(1_000),
+                floor_price: Uint128::new(500),
+                decay,
+                start: None,
+                end: app.block_info().time.seconds() + WINDOW,
+            };
+            let user = app.api().addr_make(USER);
+            app.execute_contract(user, cw_template_contract.addr(), &msg, &[]).unwrap();
+        }
+
+        fn bid(app: &mut App, cw_template_contract: &CwTemplateContract, bidder: &Addr, amount: u128) -> AnyResult<()> {
+            let msg = ExecuteMsg::Bid { auction_id: 1 };
+            app.execute_contract(bidder.clone(), cw_template_contract.addr(), &msg, &[Coin::new(amount, NATIVE_DENOM)])
+                .map(|_| ())
+        }
+
+        fn close(app: &mut App, cw_template_contract: &CwTemplateContract) -> AnyResult<()> {
+            close_page(app, cw_template_contract, None)
+        }
+
+        fn close_page(app: &mut App, cw_template_contract: &CwTemplateContract, limit: Option<u32>) -> AnyResult<()> {
+            let msg = ExecuteMsg::CloseAuction { auction_id: 1, limit };
+            let admin = app.api().addr_make(ADMIN);
+            app.execute_contract(admin, cw_template_contract.addr(), &msg, &[]).map(|_| ())
+        }
+
+        fn auction_price(app: &App, cw_template_contract: &CwTemplateContract) -> AuctionPriceResponse {
+            app.wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::AuctionPrice { auction_id: 1 })
+                .unwrap()
+        }
+
+        fn advance(app: &mut App, seconds: u64) {
+            app.update_block(|block| block.time = block.time.plus_seconds(seconds));
+        }
+
+        #[test]
+        fn sold_out_auction_clears_at_uniform_price() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            open_auction(&mut app, &cw_template_contract, PriceDecay::Linear);
+            let bidder_a = fund_investor(&mut app, BIDDER_A, 10_000);
+            let bidder_b = fund_investor(&mut app, BIDDER_B, 10_000);
+
+            bid(&mut app, &cw_template_contract, &bidder_a, 4_000).unwrap();
+
+            // 40% into the window the price has decayed by 40% of the way to the floor
+            advance(&mut app, 400);
+            let price = auction_price(&app, &cw_template_contract);
+            assert_eq!(price.price, Uint128::new(800));
+            assert_eq!((price.committed, price.filled, price.sold_out), (Uint128::new(4_000), 5, false));
+
+            // Only what buys the remaining supply at 800 is locked, the rest is refunded
+            bid(&mut app, &cw_template_contract, &bidder_b, 6_000).unwrap();
+            assert_eq!(native_balance(&app, &bidder_b), Uint128::new(6_000));
+            let price = auction_price(&app, &cw_template_contract);
+            assert_eq!((price.price, price.filled, price.sold_out), (Uint128::new(800), 10, true));
+
+            let err = bid(&mut app, &cw_template_contract, &bidder_b, 1_000).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::AuctionSoldOut { auction_id: 1 });
+
+            // A sold out auction closes before the end of the window, everyone pays 800 a unit
+            let user = app.api().addr_make(USER);
+            let issuer_balance = native_balance(&app, &user);
+            close(&mut app, &cw_template_contract).unwrap();
+            assert_eq!(native_balance(&app, &user), issuer_balance + Uint128::new(8_000));
+            let mut holders = vec![];
+            for bond_id in [1, 2] {
+                let bond = cw_template_contract.bond::<_, Empty>(&app, bond_id).unwrap();
+                holders.push(bond.holder);
+                assert_eq!(bond.face_value, Uint128::new(5_000));
+                assert_eq!(bond.interest_rate, INTEREST_RATE);
+                assert_eq!(bond.escrow, Uint128::zero());
+            }
+            holders.sort();
+            let mut bidders = vec![bidder_a, bidder_b];
+            bidders.sort();
+            assert_eq!(holders, bidders);
+
+            let auction: AuctionResponse = app
+                .wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::Auction { auction_id: 1 })
+                .unwrap();
+            assert_eq!(auction.status, AuctionStatus::Settled);
+            assert_eq!(auction.clearing_price, Some(Uint128::new(800)));
+        }
+
+        #[test]
+        fn stepwise_auction_clears_at_floor() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            open_auction(&mut app, &cw_template_contract, PriceDecay::Stepwise { interval: 300 });
+            let bidder = fund_investor(&mut app, BIDDER_A, 10_000);
+            bid(&mut app, &cw_template_contract, &bidder, 1_200).unwrap();
+
+            // The price holds for each step of 300 seconds
+            advance(&mut app, 299);
+            assert_eq!(auction_price(&app, &cw_template_contract).price, Uint128::new(1_000));
+            advance(&mut app, 1);
+            assert_eq!(auction_price(&app, &cw_template_contract).price, Uint128::new(850));
+
+            // Bidding continues until the window ends
+            let err = close(&mut app, &cw_template_contract).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "AUCTION_NOT_ENDED");
+            advance(&mut app, WINDOW - 300);
+            let err = bid(&mut app, &cw_template_contract, &bidder, 1_000).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "OUTSIDE_BIDDING_WINDOW");
+            assert_eq!(auction_price(&app, &cw_template_contract).price, Uint128::new(500));
+
+            // Undersubscribed, the bidder buys 2 units at the floor and gets the change back
+            close(&mut app, &cw_template_contract).unwrap();
+            assert_eq!(native_balance(&app, &bidder), Uint128::new(9_000));
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 1).unwrap();
+            assert_eq!(bond.face_value, Uint128::new(2_000));
+        }
+
+        #[test]
+        fn auction_closes_in_pages_at_one_price() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            open_auction(&mut app, &cw_template_contract, PriceDecay::Linear);
+            let bidder_a = fund_investor(&mut app, BIDDER_A, 10_000);
+            let bidder_b = fund_investor(&mut app, BIDDER_B, 10_000);
+            bid(&mut app, &cw_template_contract, &bidder_a, 4_000).unwrap();
+            advance(&mut app, 400);
+            bid(&mut app, &cw_template_contract, &bidder_b, 4_000).unwrap();
+            let user = app.api().addr_make(USER);
+            let issuer_balance = native_balance(&app, &user);
+
+            // Sold out at 800, the first page settles one bidder and fixes the price
+            let closed_at = app.block_info().time.seconds();
+            close_page(&mut app, &cw_template_contract, Some(1)).unwrap();
+            let query = QueryMsg::Auction { auction_id: 1 };
+            let auction: AuctionResponse = app.wrap().query_wasm_smart(cw_template_contract.addr(), &query).unwrap();
+            assert_eq!(auction.status, AuctionStatus::Closing);
+            assert_eq!(auction.clearing_price, Some(Uint128::new(800)));
+            assert!(cw_template_contract.bond::<_, Empty>(&app, 2).is_err());
+
+            // Later pages keep the clearing price, although the auction price decayed, and
+            // issue their bonds when closing started
+            advance(&mut app, 300);
+            close_page(&mut app, &cw_template_contract, Some(1)).unwrap();
+            let auction: AuctionResponse = app.wrap().query_wasm_smart(cw_template_contract.addr(), &query).unwrap();
+            assert_eq!(auction.status, AuctionStatus::Settled);
+            assert_eq!(native_balance(&app, &user), issuer_balance + Uint128::new(8_000));
+            for bond_id in [1, 2] {
+                let bond = cw_template_contract.bond::<_, Empty>(&app, bond_id).unwrap();
+                assert_eq!(bond.face_value, Uint128::new(5_000));
+                assert_eq!(bond.issued_at, closed_at);
+            }
+            let err = close(&mut app, &cw_template_contract).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::AuctionNotOpen { auction_id: 1 });
+        }
+    }
+
+    mod order_tests {
+        use super::*;
+        use crate::msg::{OrderResponse, OrdersResponse};
+        use cw721::Expiration;
+
+        const BUYER: &str = "buyer";
+
+        // List the user's bond 1 for sale in the native denom
+        fn list_bond(app: &mut App, cw_template_contract: &CwTemplateContract, price: u128, expires: Option<Expiration>) {
+            let msg = ExecuteMsg::ListBond {
+                bond_id: 1,
+                denom: Denom::Native(NATIVE_DENOM.to_string()),
+                price: Uint128::new(price),
+                expires,
+            };
+            let user = app.api().addr_make(USER);
+            app.execute_contract(user, cw_template_contract.addr(), &msg, &[]).unwrap();
+        }
+
+        // Give the buyer native coins and let the bond contract pull one transfer fee in SeiX
+        fn fund_buyer(app: &mut App, cw_template_contract: &CwTemplateContract, seix_addr: &Addr, amount: u128) -> Addr {
+            let buyer = fund_investor(app, BUYER, amount);
+            let admin = app.api().addr_make(ADMIN);
+            let msg = Cw20ExecuteMsg::Transfer { recipient: buyer.to_string(), amount: Uint128::new(5) };
+            app.execute_contract(admin, seix_addr.clone(), &msg, &[]).unwrap();
+            let msg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: cw_template_contract.addr().to_string(),
+                amount: Uint128::new(5),
+                expires: None,
+            };
+            app.execute_contract(buyer.clone(), seix_addr.clone(), &msg, &[]).unwrap();
+            buyer
+        }
+
+        fn buy(app: &mut App, cw_template_contract: &CwTemplateContract, buyer: &Addr, order_id: u64, amount: u128) -> AnyResult<()> {
+            let msg = ExecuteMsg::BuyBond { order_id };
+            app.execute_contract(buyer.clone(), cw_template_contract.addr(), &msg, &[Coin::new(amount, NATIVE_DENOM)])
+                .map(|_| ())
+        }
+
+        fn order_ids(app: &App, cw_template_contract: &CwTemplateContract, msg: &QueryMsg) -> Vec<u64> {
+            let response: OrdersResponse = app.wrap().query_wasm_smart(cw_template_contract.addr(), msg).unwrap();
+            response.orders.into_iter().map(|order| order.order_id).collect()
+        }
+
+        #[test]
+        fn list_and_buy_bond() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+            list_bond(&mut app, &cw_template_contract, 50_000, None);
+
+            let order: OrderResponse = app
+                .wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::Order { order_id: 1 })
+                .unwrap();
+            let user = app.api().addr_make(USER);
+            assert_eq!((order.bond_id, order.seller.clone(), order.price), (1, user.clone(), Uint128::new(50_000)));
+            assert_eq!(order.expires, Expiration::Never {});
+
+            // The payment must match the ask price exactly
+            let buyer = fund_buyer(&mut app, &cw_template_contract, &seix_addr, 60_000);
+            let err = buy(&mut app, &cw_template_contract, &buyer, 1, 60_000).unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::InvalidPayment { price: Uint128::new(50_000), received: Uint128::new(60_000) }
+            );
+
+            let seller_balance = native_balance(&app, &user);
+            buy(&mut app, &cw_template_contract, &buyer, 1, 50_000).unwrap();
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 1).unwrap();
+            assert_eq!(bond.holder, buyer);
+            assert_eq!(native_balance(&app, &user), seller_balance + Uint128::new(50_000));
+            assert_eq!(native_balance(&app, &buyer), Uint128::new(10_000));
+
+            // The buyer paid the transfer fee on top of the mint fee
+            let treasury = app.api().addr_make(PLATFORM_TREASURY);
+            assert_eq!(seix_balance(&app, &seix_addr, &treasury), Uint128::new(10 + 5));
+
+            // The filled order is closed
+            let err = buy(&mut app, &cw_template_contract, &buyer, 1, 10_000).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::OrderNotFound { order_id: 1 });
+        }
+
+        #[test]
+        fn buy_bond_with_seix() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+            let msg = ExecuteMsg::ListBond {
+                bond_id: 1,
+                denom: Denom::Cw20(seix_addr.clone()),
+                price: Uint128::new(1_000),
+                expires: None,
+            };
+            let user = app.api().addr_make(USER);
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &msg, &[]).unwrap();
+
+            // The SeiX sent covers the transfer fee and the ask price
+            let admin = app.api().addr_make(ADMIN);
+            let buy_msg = ReceiveMsg::BuyBond { order_id: 1 };
+            send_seix(&mut app, &seix_addr, &admin, &cw_template_contract, 1_000, &buy_msg).unwrap_err();
+            send_seix(&mut app, &seix_addr, &admin, &cw_template_contract, 1_005, &buy_msg).unwrap();
+
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 1).unwrap();
+            assert_eq!(bond.holder, admin);
+            assert_eq!(seix_balance(&app, &seix_addr, &user), Uint128::new(1_000_000 - 10 + 1_000));
+            let treasury = app.api().addr_make(PLATFORM_TREASURY);
+            assert_eq!(seix_balance(&app, &seix_addr, &treasury), Uint128::new(10 + 5));
+        }
+
+        #[test]
+        fn open_orders_by_bond_seller_and_price() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+            let expires = Expiration::AtTime(app.block_info().time.plus_seconds(60));
+            list_bond(&mut app, &cw_template_contract, 70_000, None);
+            list_bond(&mut app, &cw_template_contract, 60_000, Some(expires));
+            list_bond(&mut app, &cw_template_contract, 80_000, None);
+
+            let user = app.api().addr_make(USER);
+            let by_bond = QueryMsg::OrdersByBond { bond_id: 1, start_after: None, limit: None };
+            let by_seller = QueryMsg::OrdersBySeller { seller: user.to_string(), start_after: None, limit: None };
+            assert_eq!(order_ids(&app, &cw_template_contract, &by_bond), vec![1, 2, 3]);
+            assert_eq!(order_ids(&app, &cw_template_contract, &by_seller), vec![1, 2, 3]);
+
+            // Lowest ask first, paginated within the same ordering
+            let denom = Denom::Native(NATIVE_DENOM.to_string());
+            let by_price = |max_price: Option<u128>, start_after| QueryMsg::OrdersByPrice {
+                denom: denom.clone(),
+                max_price: max_price.map(Uint128::new),
+                start_after,
+                limit: Some(2),
+            };
+            assert_eq!(order_ids(&app, &cw_template_contract, &by_price(None, None)), vec![2, 1]);
+            assert_eq!(order_ids(&app, &cw_template_contract, &by_price(None, Some(1))), vec![3]);
+            assert_eq!(order_ids(&app, &cw_template_contract, &by_price(Some(70_000), Some(2))), vec![1]);
+
+            // Only the seller can cancel an order
+            let cancel = ExecuteMsg::CancelOrder { order_id: 3 };
+            let admin = app.api().addr_make(ADMIN);
+            app.execute_contract(admin, cw_template_contract.addr(), &cancel, &[]).unwrap_err();
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &cancel, &[]).unwrap();
+
+            // Expired orders are no longer listed and cannot be filled
+            app.update_block(|block| block.time = block.time.plus_seconds(60));
+            assert_eq!(order_ids(&app, &cw_template_contract, &by_price(None, None)), vec![1]);
+            let buyer = fund_buyer(&mut app, &cw_template_contract, &seix_addr, 60_000);
+            let err = buy(&mut app, &cw_template_contract, &buyer, 2, 60_000).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::OrderExpired { order_id: 2 });
+
+            // Transferring the bond cancels its remaining orders
+            let transfer = ReceiveMsg::TransferBond { bond_id: 1, new_holder: buyer.to_string() };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 5, &transfer).unwrap();
+            assert!(order_ids(&app, &cw_template_contract, &by_seller).is_empty());
+        }
+    }
+
+    mod call_tests {
+        use super::*;
+        use crate::state::CallTerms;
+        use cosmwasm_std::Event;
+
+        // Mint the standard bond from the user with the given call terms
+        fn mint_callable_bond(app: &mut App, cw_template_contract: &CwTemplateContract, call_terms: CallTerms) -> AnyResult<AppResponse> {
+            let terms = BondTerms { call_terms: Some(call_terms), ..standard_terms() };
+            mint_bond_with_terms(app, cw_template_contract, terms, REQUIRED_FUNDING)
+        }
+
+        #[test]
+        fn call_bond_after_notice() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            let call_terms = CallTerms {
+                protection: 90 * DAY,
+                schedule: schedule(&[(0, 1_020), (180 * DAY, 1_010)]),
+                notice: 30 * DAY,
+            };
+            mint_callable_bond(&mut app, &cw_template_contract, call_terms).unwrap();
+            let issued_at = app.block_info().time.seconds();
+
+            // Sell the bond on to a holder
+            let user = app.api().addr_make(USER);
+            let holder = app.api().addr_make(HOLDER);
+            let transfer = ReceiveMsg::TransferBond { bond_id: 1, new_holder: holder.to_string() };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 5, &transfer).unwrap();
+
+            // Only the issuer calls, and only after giving notice
+            let call = ExecuteMsg::CallBond { bond_id: 1 };
+            let err = execute(&mut app, &cw_template_contract, USER, &call, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::CallNoticeRequired {});
+            let notice = ExecuteMsg::NoticeCall { bond_id: 1 };
+            let err = execute(&mut app, &cw_template_contract, HOLDER, &notice, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::Unauthorized {});
+
+            // Notice given during the call protection calls the bond as soon as it ends
+            let call_date = issued_at + 90 * DAY;
+            let res = execute(&mut app, &cw_template_contract, USER, &notice, &[]).unwrap();
+            assert!(res.has_event(
+                &Event::new("wasm-bond_call_notice")
+                    .add_attribute("holder", holder.to_string())
+                    .add_attribute("call_date", call_date.to_string())
+                    .add_attribute("call_price", "1.02")
+            ));
+            assert_eq!(cw_template_contract.bond::<_, Empty>(&app, 1).unwrap().call_date, Some(call_date));
+            let err = execute(&mut app, &cw_template_contract, USER, &notice, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::CallNoticeGiven { call_date });
+
+            let err = execute(&mut app, &cw_template_contract, USER, &call, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::CallNotDue { call_date });
+
+            // The holder gets 102% of the face value plus 90 days of interest,
+            // the issuer the rest of the escrow
+            app.update_block(|block| block.time = block.time.plus_seconds(90 * DAY));
+            let issuer_balance = native_balance(&app, &user);
+            let res = execute(&mut app, &cw_template_contract, USER, &call, &[]).unwrap();
+            assert!(res.has_event(
+                &Event::new("wasm-bond_called")
+                    .add_attribute("accrued_interest", "2465")
+                    .add_attribute("payout", "104465")
+            ));
+            assert_eq!(native_balance(&app, &holder), Uint128::new(104_465));
+            assert_eq!(native_balance(&app, &user), issuer_balance + Uint128::new(REQUIRED_FUNDING - 104_465));
+            assert_eq!(cw_template_contract.bond::<_, Empty>(&app, 1).unwrap().status, BondStatus::Called);
+        }
+
+        #[test]
+        fn late_call_pays_announced_price() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            let call_terms = CallTerms {
+                protection: 90 * DAY,
+                schedule: schedule(&[(0, 1_020), (180 * DAY, 1_010)]),
+                notice: 30 * DAY,
+            };
+            mint_callable_bond(&mut app, &cw_template_contract, call_terms).unwrap();
+            execute(&mut app, &cw_template_contract, USER, &ExecuteMsg::NoticeCall { bond_id: 1 }, &[]).unwrap();
+
+            // Called past the step down of the schedule, the price announced for day 90
+            // still applies while interest accrues until the call
+            app.update_block(|block| block.time = block.time.plus_seconds(200 * DAY));
+            let res = execute(&mut app, &cw_template_contract, USER, &ExecuteMsg::CallBond { bond_id: 1 }, &[])
+                .unwrap();
+            assert!(res.has_event(
+                &Event::new("wasm-bond_called")
+                    .add_attribute("call_price", "1.02")
+                    .add_attribute("accrued_interest", "5479")
+                    .add_attribute("payout", "107479")
+            ));
+        }
+
+        #[test]
+        fn call_without_notice() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+
+            // Call terms must fit within the life of the bond
+            let call_terms = CallTerms { protection: MATURITY, schedule: schedule(&[(0, 1_000)]), notice: 0 };
+            let err = mint_callable_bond(&mut app, &cw_template_contract, call_terms).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "INVALID_CALL_TERMS");
+
+            // A plain bond has no call provisions
+            mint_native_bond(&mut app, &cw_template_contract);
+            let err = execute(&mut app, &cw_template_contract, USER, &ExecuteMsg::CallBond { bond_id: 1 }, &[])
+                .unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::NotCallable { bond_id: 1 });
+
+            let call_terms = CallTerms { protection: 10 * DAY, schedule: schedule(&[(0, 1_200)]), notice: 0 };
+            mint_callable_bond(&mut app, &cw_template_contract, call_terms).unwrap();
+            let call = ExecuteMsg::CallBond { bond_id: 2 };
+            let err = execute(&mut app, &cw_template_contract, USER, &call, &[]).unwrap_err();
+            let until = app.block_info().time.seconds() + 10 * DAY;
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::CallProtected { until });
+
+            // A call price above the escrow is topped up by the issuer
+            app.update_block(|block| block.time = block.time.plus_seconds(10 * DAY));
+            let err = execute(&mut app, &cw_template_contract, USER, &call, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "INSUFFICIENT_FUNDS");
+            let user = app.api().addr_make(USER);
+            let user_balance = native_balance(&app, &user);
+            let res = execute(&mut app, &cw_template_contract, USER, &call, &[Coin::new(20_000u128, NATIVE_DENOM)])
+                .unwrap();
+
+            // The issuer is also the holder here: paid 120% plus 10 days of interest,
+            // then refunded the rest of the escrow and top-up
+            assert!(res.has_event(&Event::new("wasm-bond_called").add_attribute("payout", "120273")));
+            assert_eq!(native_balance(&app, &user), user_balance + Uint128::new(REQUIRED_FUNDING));
+        }
+    }
+
+    mod put_tests {
+        use super::*;
+        use crate::state::{PutTerms, PutWindow};
+        use cosmwasm_std::Event;
+
+        // Mint the standard bond from the user with the given put terms
+        fn mint_puttable_bond(app: &mut App, cw_template_contract: &CwTemplateContract, put_terms: PutTerms) -> AnyResult<AppResponse> {
+            let terms = BondTerms { put_terms: Some(put_terms), ..standard_terms() };
+            mint_bond_with_terms(app, cw_template_contract, terms, REQUIRED_FUNDING)
+        }
+
+        // Put terms with a single window and a price schedule given in permille of face value
+        fn put_terms(start: u64, end: u64, prices: &[(u64, u64)]) -> PutTerms {
+            PutTerms { windows: vec![PutWindow { start, end }], schedule: schedule(prices) }
+        }
+
+        #[test]
+        fn put_bond_in_parts() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            let terms = put_terms(180 * DAY, 200 * DAY, &[(0, 950), (190 * DAY, 980)]);
+            mint_puttable_bond(&mut app, &cw_template_contract, terms).unwrap();
+
+            // Sell the bond on to a holder, who pays the redeem fees in SeiX
+            let user = app.api().addr_make(USER);
+            let holder = app.api().addr_make(HOLDER);
+            let transfer = ReceiveMsg::TransferBond { bond_id: 1, new_holder: holder.to_string() };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 5, &transfer).unwrap();
+            let seix_transfer = Cw20ExecuteMsg::Transfer { recipient: holder.to_string(), amount: Uint128::new(30) };
+            app.execute_contract(user.clone(), seix_addr.clone(), &seix_transfer, &[]).unwrap();
+
+            let put = |amount: Option<u128>| ReceiveMsg::PutBond { bond_id: 1, amount: amount.map(Uint128::new) };
+            let err = send_seix(&mut app, &seix_addr, &holder, &cw_template_contract, 15, &put(None)).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::OutsidePutWindow {});
+
+            app.update_block(|block| block.time = block.time.plus_seconds(180 * DAY));
+            let err = app
+                .execute_contract(user.clone(), cw_template_contract.addr(), &ExecuteMsg::PutBond { bond_id: 1, amount: None }, &[])
+                .unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::NotHolder { action: "put".to_string() });
+            let err = send_seix(&mut app, &seix_addr, &holder, &cw_template_contract, 15, &put(Some(200_000))).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "INVALID_PUT_AMOUNT");
+
+            // Putting 40% pays 95% of that part plus its 180 days of interest, the issuer gets
+            // back what the escrow holds beyond the 60_000 face value and its interest to maturity
+            let issuer_balance = native_balance(&app, &user);
+            send_seix(&mut app, &seix_addr, &holder, &cw_template_contract, 15, &put(Some(40_000))).unwrap();
+            assert_eq!(native_balance(&app, &holder), Uint128::new(38_000 + 1_972));
+            assert_eq!(native_balance(&app, &user), issuer_balance + Uint128::new(4_028));
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 1).unwrap();
+            assert_eq!(bond.face_value, Uint128::new(60_000));
+            assert_eq!(bond.escrow, Uint128::new(66_000));
+
+            // The rest is put at the later price of 98%, closing the bond
+            app.update_block(|block| block.time = block.time.plus_seconds(10 * DAY));
+            let res = app
+                .execute_contract(
+                    holder.clone(),
+                    seix_addr.clone(),
+                    &Cw20ExecuteMsg::Send {
+                        contract: cw_template_contract.addr().to_string(),
+                        amount: Uint128::new(15),
+                        msg: to_json_binary(&put(None)).unwrap(),
+                    },
+                    &[],
+                )
+                .unwrap();
+            assert!(res.has_event(
+                &Event::new("wasm-bond_put")
+                    .add_attribute("amount", "60000")
+                    .add_attribute("put_price", "0.98")
+                    .add_attribute("accrued_interest", "3123")
+                    .add_attribute("payout", "61923")
+                    .add_attribute("released", "4077")
+            ));
+            assert_eq!(native_balance(&app, &user), issuer_balance + Uint128::new(4_028 + 4_077));
+            assert_eq!(cw_template_contract.bond::<_, Empty>(&app, 1).unwrap().status, BondStatus::Redeemed);
+            let treasury = app.api().addr_make(PLATFORM_TREASURY);
+            assert_eq!(seix_balance(&app, &seix_addr, &treasury), Uint128::new(10 + 5 + 15 + 15));
+        }
+
+        #[test]
+        fn put_terms_are_checked() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+
+            // Put windows must close by maturity
+            let terms = put_terms(180 * DAY, MATURITY + DAY, &[(0, 950)]);
+            let err = mint_puttable_bond(&mut app, &cw_template_contract, terms).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "INVALID_PUT_TERMS");
+
+            // A plain bond has no put provisions
+            mint_native_bond(&mut app, &cw_template_contract);
+            let user = app.api().addr_make(USER);
+            let put = ExecuteMsg::PutBond { bond_id: 1, amount: None };
+            let err = app.execute_contract(user.clone(), cw_template_contract.addr(), &put, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::NotPuttable { bond_id: 1 });
+
+            // Once its window has closed the bond can no longer be put
+            mint_puttable_bond(&mut app, &cw_template_contract, put_terms(0, 30 * DAY, &[(0, 1_000)])).unwrap();
+            app.update_block(|block| block.time = block.time.plus_seconds(30 * DAY));
+            let put = ExecuteMsg::PutBond { bond_id: 2, amount: None };
+            let err = app.execute_contract(user, cw_template_contract.addr(), &put, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::OutsidePutWindow {});
+        }
+    }
+
+    mod collateral_tests {
+        use super::*;
+        use crate::state::Collateral;
+        use cosmwasm_std::Event;
+
+        // Mint the standard bond from the user with `funds` native coins attached
+        fn mint_collateralized_bond(
+            app: &mut App,
+            cw_template_contract: &CwTemplateContract,
+            collateral: Collateral,
+            funds: u128,
+        ) -> AnyResult<AppResponse> {
+            let terms = BondTerms { collateral: Some(collateral), ..standard_terms() };
+            mint_bond_with_terms(app, cw_template_contract, terms, funds)
+        }
+
+        fn collateral(denom: Denom, amount: u128, ratio: Decimal) -> Collateral {
+            Collateral { denom, amount: Uint128::new(amount), ratio }
+        }
+
+        #[test]
+        fn native_collateral_is_returned_on_redemption() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            let native = Denom::Native(NATIVE_DENOM.to_string());
+            let ratio = Decimal::percent(150);
+
+            // The collateral is attached in the same denomination as the principal
+            let terms = collateral(native.clone(), 140_000, ratio);
+            let err = mint_collateralized_bond(&mut app, &cw_template_contract, terms, REQUIRED_FUNDING + 140_000)
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::Undercollateralized { required: Uint128::new(150_000), locked: Uint128::new(140_000) }
+            );
+            let terms = collateral(native.clone(), 160_000, ratio);
+            let err = mint_collateralized_bond(&mut app, &cw_template_contract, terms.clone(), 150_000).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "INVALID_COLLATERAL");
+            mint_collateralized_bond(&mut app, &cw_template_contract, terms, REQUIRED_FUNDING + 160_000).unwrap();
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 1).unwrap();
+            assert_eq!(bond.escrow, Uint128::new(REQUIRED_FUNDING));
+            assert_eq!(bond.collateral.unwrap().amount, Uint128::new(160_000));
+
+            // Only the issuer withdraws, and only what the ratio does not require
+            let withdraw = ExecuteMsg::WithdrawExcessCollateral { bond_id: 1 };
+            let err = execute(&mut app, &cw_template_contract, HOLDER, &withdraw, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::Unauthorized {});
+            let user = app.api().addr_make(USER);
+            let user_balance = native_balance(&app, &user);
+            execute(&mut app, &cw_template_contract, USER, &withdraw, &[]).unwrap();
+            assert_eq!(native_balance(&app, &user), user_balance + Uint128::new(10_000));
+            let err = execute(&mut app, &cw_template_contract, USER, &withdraw, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::NoExcessCollateral {});
+
+            let top_up = ExecuteMsg::TopUpCollateral { bond_id: 1 };
+            execute(&mut app, &cw_template_contract, USER, &top_up, &[Coin::new(5_000u128, NATIVE_DENOM)]).unwrap();
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 1).unwrap();
+            assert_eq!(bond.collateral.unwrap().amount, Uint128::new(155_000));
+
+            // A repaid bond cannot be liquidated, redeeming it returns the collateral
+            app.update_block(|block| block.time = block.time.plus_seconds(MATURITY + 30 * DAY));
+            let claim = ExecuteMsg::ClaimCollateral { bond_id: 1 };
+            let err = execute(&mut app, &cw_template_contract, USER, &claim, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::BondRepaid {});
+            let user_balance = native_balance(&app, &user);
+            let redeem = ReceiveMsg::RedeemBond { bond_id: 1 };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 15, &redeem).unwrap();
+            assert_eq!(native_balance(&app, &user), user_balance + Uint128::new(REQUIRED_FUNDING + 155_000));
+        }
+
+        #[test]
+        fn holder_claims_collateral_on_default() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            set_default_policy(&mut app, &cw_template_contract, 30 * DAY, Decimal::zero());
+
+            // The issuer escrows half of what is owed and locks SeiX pulled from their allowance
+            let user = app.api().addr_make(USER);
+            let allowance = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: cw_template_contract.addr().to_string(),
+                amount: Uint128::new(200_000),
+                expires: None,
+            };
+            app.execute_contract(user.clone(), seix_addr.clone(), &allowance, &[]).unwrap();
+            let terms = collateral(Denom::Cw20(seix_addr.clone()), 200_000, Decimal::percent(200));
+            mint_collateralized_bond(&mut app, &cw_template_contract, terms, 55_000).unwrap();
+            let user_seix = seix_balance(&app, &seix_addr, &user);
+
+            let holder = app.api().addr_make(HOLDER);
+            let transfer = ReceiveMsg::TransferBond { bond_id: 1, new_holder: holder.to_string() };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 5, &transfer).unwrap();
+
+            // The bond cannot be redeemed at maturity, nor liquidated before the grace period ends
+            app.update_block(|block| block.time = block.time.plus_seconds(MATURITY));
+            let redeem = ExecuteMsg::RedeemBond { bond_id: 1 };
+            let err = execute(&mut app, &cw_template_contract, HOLDER, &redeem, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "INSUFFICIENT_FUNDS");
+            let claim = ExecuteMsg::ClaimCollateral { bond_id: 1 };
+            let err = execute(&mut app, &cw_template_contract, HOLDER, &claim, &[]).unwrap_err();
+            let ends_at = app.block_info().time.seconds() + 30 * DAY;
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::GracePeriodNotOver { ends_at });
+
+            // Half of the bond is unpaid, so the holder gets the escrow and half the collateral
+            app.update_block(|block| block.time = block.time.plus_seconds(30 * DAY + 1));
+            let err = execute(&mut app, &cw_template_contract, USER, &claim, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::NotHolder { action: "liquidate".to_string() });
+            let res = execute(&mut app, &cw_template_contract, HOLDER, &claim, &[]).unwrap();
+            assert!(res.has_event(
+                &Event::new("wasm-collateral_claimed")
+                    .add_attribute("shortfall", "55000")
+                    .add_attribute("claimed", "100000")
+                    .add_attribute("returned", "100000")
+            ));
+            assert_eq!(native_balance(&app, &holder), Uint128::new(55_000));
+            assert_eq!(seix_balance(&app, &seix_addr, &holder), Uint128::new(100_000));
+            assert_eq!(seix_balance(&app, &seix_addr, &user), user_seix - Uint128::new(5) + Uint128::new(100_000));
+            assert_eq!(cw_template_contract.bond::<_, Empty>(&app, 1).unwrap().status, BondStatus::Defaulted);
+        }
+
+        #[test]
+        fn spoofed_mint_cannot_lock_collateral() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+
+            // The user allows the contract to pull SeiX for collateral
+            let user = app.api().addr_make(USER);
+            let allowance = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: cw_template_contract.addr().to_string(),
+                amount: Uint128::new(200_000),
+                expires: None,
+            };
+            app.execute_contract(user.clone(), seix_addr.clone(), &allowance, &[]).unwrap();
+            let user_seix = seix_balance(&app, &seix_addr, &user);
+
+            // A contract delivering a hook in the user's name cannot lock it
+            let fake_token = app.api().addr_make("fake_token");
+            let mint = ReceiveMsg::MintBond {
+                face_value: Uint128::new(FACE_VALUE),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: None,
+                day_count: None,
+                call_terms: None,
+                put_terms: None,
+                collateral: Some(collateral(Denom::Cw20(seix_addr.clone()), 200_000, Decimal::percent(200))),
+                floating_rate: None,
+            };
+            let spoofed = ExecuteMsg::Receive(cw20::Cw20ReceiveMsg {
+                sender: user.to_string(),
+                amount: Uint128::new(REQUIRED_FUNDING),
+                msg: to_json_binary(&mint).unwrap(),
+            });
+            let err = app
+                .execute_contract(fake_token.clone(), cw_template_contract.addr(), &spoofed, &[])
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::UntrustedToken { token: fake_token.to_string() }
+            );
+            assert_eq!(seix_balance(&app, &seix_addr, &user), user_seix);
+            assert!(cw_template_contract.bond::<_, Empty>(&app, 1).is_err());
+        }
+    }
+
+    mod lifecycle_tests {
+        use super::*;
+        use crate::msg::BondsResponse;
+        use crate::state::Collateral;
+        use cw721::msg::TokensResponse;
+
+        fn bonds_by_status(app: &App, cw_template_contract: &CwTemplateContract, status: BondStatus) -> Vec<u64> {
+            let response: BondsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::BondsByStatus { status, start_after: None, limit: None },
+                )
+                .unwrap();
+            response.bonds.into_iter().map(|info| info.bond_id).collect()
+        }
+
+        fn status(app: &App, cw_template_contract: &CwTemplateContract, bond_id: u64) -> BondStatus {
+            cw_template_contract.bond::<_, Empty>(app, bond_id).unwrap().status
+        }
+
+        #[test]
+        fn redeemed_bond_stays_on_record() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            mint_native_bond(&mut app, &cw_template_contract);
+            mint_native_bond(&mut app, &cw_template_contract);
+            assert_eq!(status(&app, &cw_template_contract, 1), BondStatus::Active);
+
+            // Outstanding bonds are reported as matured from their maturity on
+            app.update_block(|block| block.time = block.time.plus_seconds(MATURITY));
+            assert_eq!(status(&app, &cw_template_contract, 1), BondStatus::Matured);
+            assert_eq!(bonds_by_status(&app, &cw_template_contract, BondStatus::Matured), vec![1, 2]);
+            assert!(bonds_by_status(&app, &cw_template_contract, BondStatus::Active).is_empty());
+
+            let user = app.api().addr_make(USER);
+            let redeem = ReceiveMsg::RedeemBond { bond_id: 1 };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 15, &redeem).unwrap();
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 1).unwrap();
+            assert_eq!(bond.status, BondStatus::Redeemed);
+            assert_eq!(bond.escrow, Uint128::zero());
+            assert_eq!(bonds_by_status(&app, &cw_template_contract, BondStatus::Redeemed), vec![1]);
+            assert_eq!(bonds_by_status(&app, &cw_template_contract, BondStatus::Matured), vec![2]);
+
+            // A settled bond can no longer be operated on and is no longer a token
+            let err = send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 15, &redeem).unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::BondClosed { bond_id: 1, status: BondStatus::Redeemed }
+            );
+            let tokens: TokensResponse = app
+                .wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::AllTokens { start_after: None, limit: None })
+                .unwrap();
+            assert_eq!(tokens.tokens, vec!["2".to_string()]);
+        }
+
+        #[test]
+        fn pending_and_cancelled_bonds() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+
+            // A collateralized bond escrowed in part is pending until the issuer repays the rest
+            approve_mint_fee(&mut app, &cw_template_contract);
+            let collateral = Collateral {
+                denom: Denom::Native(NATIVE_DENOM.to_string()),
+                amount: Uint128::new(FACE_VALUE),
+                ratio: Decimal::one(),
+            };
+            let msg = ExecuteMsg::MintBond {
+                face_value: Uint128::new(FACE_VALUE),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: None,
+                day_count: None,
+                call_terms: None,
+                put_terms: None,
+                collateral: Some(collateral),
+                floating_rate: None,
+            };
+            let user = app.api().addr_make(USER);
+            let funds = [Coin::new(FACE_VALUE + 10_000, NATIVE_DENOM)];
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &msg, &funds).unwrap();
+            assert_eq!(status(&app, &cw_template_contract, 1), BondStatus::Pending);
+            let fund = ExecuteMsg::FundBond { bond_id: 1 };
+            let funds = [Coin::new(REQUIRED_FUNDING - 10_000, NATIVE_DENOM)];
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &fund, &funds).unwrap();
+            assert_eq!(status(&app, &cw_template_contract, 1), BondStatus::Active);
+
+            // The issuer cancels the bond while holding it, getting escrow and collateral back
+            let user_balance = native_balance(&app, &user);
+            let cancel = ExecuteMsg::CancelBond { bond_id: 1 };
+            app.execute_contract(user.clone(), cw_template_contract.addr(), &cancel, &[]).unwrap();
+            assert_eq!(native_balance(&app, &user), user_balance + Uint128::new(REQUIRED_FUNDING + FACE_VALUE));
+            assert_eq!(status(&app, &cw_template_contract, 1), BondStatus::Cancelled);
+            assert_eq!(bonds_by_status(&app, &cw_template_contract, BondStatus::Cancelled), vec![1]);
+            let err = app.execute_contract(user.clone(), cw_template_contract.addr(), &cancel, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "BOND_CLOSED");
+
+            // Once sold the bond can no longer be cancelled
+            mint_native_bond(&mut app, &cw_template_contract);
+            let holder = app.api().addr_make("holder");
+            let transfer = ReceiveMsg::TransferBond { bond_id: 2, new_holder: holder.to_string() };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 5, &transfer).unwrap();
+            let cancel = ExecuteMsg::CancelBond { bond_id: 2 };
+            let err = app.execute_contract(user, cw_template_contract.addr(), &cancel, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::Unauthorized {});
+            assert_eq!(bonds_by_status(&app, &cw_template_contract, BondStatus::Active), vec![2]);
+        }
+    }
+
+    mod default_tests {
+        use super::*;
+        use crate::msg::DefaultsResponse;
+        use crate::state::{Collateral, DefaultRecord, DefaultResolution};
+        use cosmwasm_std::Event;
+
+        // Mint the standard bond from the user escrowing `principal`, secured by native collateral
+        fn mint_underfunded_bond(app: &mut App, cw_template_contract: &CwTemplateContract, principal: u128) {
+            let collateral = Collateral {
+                denom: Denom::Native(NATIVE_DENOM.to_string()),
+                amount: Uint128::new(FACE_VALUE),
+                ratio: Decimal::one(),
+            };
+            let terms = BondTerms { collateral: Some(collateral), ..standard_terms() };
+            mint_bond_with_terms(app, cw_template_contract, terms, principal + FACE_VALUE).unwrap();
+        }
+
+        fn defaults(app: &App, cw_template_contract: &CwTemplateContract) -> Vec<DefaultRecord> {
+            let issuer = app.api().addr_make(USER).to_string();
+            let response: DefaultsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::DefaultsByIssuer { issuer, start_after: None, limit: None },
+                )
+                .unwrap();
+            response.defaults
+        }
+
+        #[test]
+        fn mark_default_and_cure() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            set_default_policy(&mut app, &cw_template_contract, 30 * DAY, Decimal::percent(20));
+            mint_underfunded_bond(&mut app, &cw_template_contract, 55_000);
+            let user = app.api().addr_make(USER);
+            let holder = fund_investor(&mut app, HOLDER, 60_000);
+            let transfer = ReceiveMsg::TransferBond { bond_id: 1, new_holder: holder.to_string() };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 5, &transfer).unwrap();
+
+            // Anyone may put the bond in default once the grace period after maturity is over
+            app.update_block(|block| block.time = block.time.plus_seconds(MATURITY + 30 * DAY));
+            let mark = ExecuteMsg::MarkDefault { bond_id: 1 };
+            let err = execute(&mut app, &cw_template_contract, "anyone", &mark, &[]).unwrap_err();
+            let ends_at = app.block_info().time.seconds();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::GracePeriodNotOver { ends_at });
+            app.update_block(|block| block.time = block.time.plus_seconds(1));
+            let res = execute(&mut app, &cw_template_contract, "anyone", &mark, &[]).unwrap();
+            assert!(res.has_event(&Event::new("wasm-bond_defaulted").add_attribute("shortfall", "55000")));
+            assert_eq!(cw_template_contract.bond::<_, Empty>(&app, 1).unwrap().status, BondStatus::Defaulted);
+            let err = execute(&mut app, &cw_template_contract, "anyone", &mark, &[]).unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::BondClosed { bond_id: 1, status: BondStatus::Defaulted }
+            );
+            let defaulted_at = app.block_info().time.seconds();
+            let record = DefaultRecord { bond_id: 1, defaulted_at, shortfall: Uint128::new(55_000), resolution: None };
+            assert_eq!(defaults(&app, &cw_template_contract), vec![record.clone()]);
+
+            // Curing costs the shortfall plus 20% a year on it since maturity
+            let cure = ExecuteMsg::CureDefault { bond_id: 1 };
+            let err = execute(&mut app, &cw_template_contract, HOLDER, &cure, &[Coin::new(60_000u128, NATIVE_DENOM)])
+                .unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::Unauthorized {});
+            let err = execute(&mut app, &cw_template_contract, USER, &cure, &[Coin::new(50_000u128, NATIVE_DENOM)])
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::InsufficientFunds { needed: Uint128::new(55_904), available: Uint128::new(50_000) }
+            );
+
+            // The holder is paid in full with the penalty, the issuer gets the surplus and collateral back
+            let user_balance = native_balance(&app, &user);
+            let res = execute(&mut app, &cw_template_contract, USER, &cure, &[Coin::new(60_000u128, NATIVE_DENOM)])
+                .unwrap();
+            assert!(res.has_event(
+                &Event::new("wasm-default_cured")
+                    .add_attribute("penalty", "904")
+                    .add_attribute("payout", "110904")
+            ));
+            assert_eq!(native_balance(&app, &holder), Uint128::new(60_000 + 110_904));
+            assert_eq!(native_balance(&app, &user), user_balance + Uint128::new(4_096 + FACE_VALUE) - Uint128::new(60_000));
+            assert_eq!(cw_template_contract.bond::<_, Empty>(&app, 1).unwrap().status, BondStatus::Redeemed);
+            let cured_at = app.block_info().time.seconds();
+            let resolution = Some(DefaultResolution::Cured { at: cured_at, penalty: Uint128::new(904) });
+            assert_eq!(defaults(&app, &cw_template_contract), vec![DefaultRecord { resolution, ..record }]);
+        }
+
+        #[test]
+        fn late_repayment_requires_cure() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            mint_underfunded_bond(&mut app, &cw_template_contract, 55_000);
+            mint_native_bond(&mut app, &cw_template_contract);
+
+            // Without a grace period the bond must be repaid by maturity
+            app.update_block(|block| block.time = block.time.plus_seconds(MATURITY + 1));
+            let fund = ExecuteMsg::FundBond { bond_id: 1 };
+            let err = execute(&mut app, &cw_template_contract, USER, &fund, &[Coin::new(55_000u128, NATIVE_DENOM)])
+                .unwrap_err();
+            let ended_at = app.block_info().time.seconds() - 1;
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::GracePeriodOver { ended_at });
+
+            // A bond repaid in full cannot be put in default
+            let mark = ExecuteMsg::MarkDefault { bond_id: 2 };
+            let err = execute(&mut app, &cw_template_contract, USER, &mark, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::BondRepaid {});
+
+            // Without a penalty rate curing costs the shortfall only
+            let user = app.api().addr_make(USER);
+            let user_balance = native_balance(&app, &user);
+            let cure = ExecuteMsg::CureDefault { bond_id: 1 };
+            execute(&mut app, &cw_template_contract, USER, &cure, &[Coin::new(55_000u128, NATIVE_DENOM)]).unwrap();
+            assert_eq!(native_balance(&app, &user), user_balance + Uint128::new(REQUIRED_FUNDING + FACE_VALUE - 55_000));
+            assert_eq!(defaults(&app, &cw_template_contract).len(), 1);
+        }
+
+        #[test]
+        fn unsecured_default_pays_out_escrow() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+
+            // An offering issues the holder an unsecured bond, the issuer only repays half of it
+            approve_mint_fee(&mut app, &cw_template_contract);
+            let native = Denom::Native(NATIVE_DENOM.to_string());
+            let open = ExecuteMsg::OpenOffering {
+                denom: native.clone(),
+                price: Decimal::one(),
+                interest_rate: INTEREST_RATE,
+                maturity: MATURITY,
+                coupon_frequency: None,
+                day_count: None,
+                start: None,
+                end: app.block_info().time.seconds() + DAY,
+                min_raise: Uint128::zero(),
+                max_raise: Uint128::new(FACE_VALUE),
+            };
+            execute(&mut app, &cw_template_contract, USER, &open, &[]).unwrap();
+            let holder = fund_investor(&mut app, HOLDER, FACE_VALUE);
+            let subscribe = ExecuteMsg::Subscribe { offering_id: 1 };
+            execute(&mut app, &cw_template_contract, HOLDER, &subscribe, &[Coin::new(FACE_VALUE, NATIVE_DENOM)]).unwrap();
+            app.update_block(|block| block.time = block.time.plus_seconds(DAY));
+            let close = ExecuteMsg::CloseOffering { offering_id: 1, limit: None };
+            execute(&mut app, &cw_template_contract, USER, &close, &[]).unwrap();
+            let fund = ExecuteMsg::FundBond { bond_id: 1 };
+            execute(&mut app, &cw_template_contract, USER, &fund, &[Coin::new(55_000u128, NATIVE_DENOM)]).unwrap();
+            let list = ExecuteMsg::ListBond { bond_id: 1, denom: native, price: Uint128::new(90_000), expires: None };
+            execute(&mut app, &cw_template_contract, HOLDER, &list, &[]).unwrap();
+
+            // The default cancels the open orders on the bond
+            app.update_block(|block| block.time = block.time.plus_seconds(MATURITY + 1));
+            execute(&mut app, &cw_template_contract, "anyone", &ExecuteMsg::MarkDefault { bond_id: 1 }, &[]).unwrap();
+            let orders: crate::msg::OrdersResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::OrdersByBond { bond_id: 1, start_after: None, limit: None },
+                )
+                .unwrap();
+            assert!(orders.orders.is_empty());
+
+            // Without collateral the holder takes the escrow
+            let res = execute(&mut app, &cw_template_contract, HOLDER, &ExecuteMsg::ClaimCollateral { bond_id: 1 }, &[])
+                .unwrap();
+            assert!(res.has_event(
+                &Event::new("wasm-collateral_claimed")
+                    .add_attribute("shortfall", "55000")
+                    .add_attribute("escrow", "55000")
+                    .add_attribute("claimed", "0")
+            ));
+            assert_eq!(native_balance(&app, &holder), Uint128::new(55_000));
+            let record = defaults(&app, &cw_template_contract).pop().unwrap();
+            let at = app.block_info().time.seconds();
+            assert_eq!(record.resolution, Some(DefaultResolution::Liquidated { at, claimed: Uint128::zero() }));
+        }
+    }
+
+    mod floating_tests {
+        use super::*;
+        use crate::msg::{AccruedInterestResponse, OracleQueryMsg, RateFixingsResponse, ReferenceRateResponse};
+        use crate::state::{FixingSource, FloatingRate, RateFixing};
+        use cosmwasm_schema::cw_serde;
+        use cosmwasm_std::{Deps, DepsMut, Env, Event, MessageInfo};
+        use cw_storage_plus::Item;
+
+        const QUARTER: u64 = MATURITY / 4;
+
+        // Sets the reference rate of the mock oracle, on instantiation and on execution
+        #[cw_serde]
+        struct SetRate {
+            rate: Decimal,
+        }
+
+        const REFERENCE_RATE: Item<Decimal> = Item::new("reference_rate");
+
+        // An oracle publishing whatever reference rate it was last given
+        fn contract_oracle() -> Box<dyn Contract<Empty>> {
+            let set_rate = |deps: DepsMut, _: Env, _: MessageInfo, msg: SetRate| {
+                REFERENCE_RATE.save(deps.storage, &msg.rate)?;
+                Ok::<_, StdError>(Response::new())
+            };
+            let contract = ContractWrapper::new(set_rate, set_rate, |deps: Deps, _: Env, msg: OracleQueryMsg| {
+                match msg {
+                    OracleQueryMsg::ReferenceRate {} => {
+                        to_json_binary(&ReferenceRateResponse { rate: REFERENCE_RATE.load(deps.storage)? })
+                    }
+                }
+            });
+            Box::new(contract)
+        }
+
+        // Deploy the oracle at the given reference rate and have the admin configure it
+        fn setup_oracle(app: &mut App, cw_template_contract: &CwTemplateContract, rate: Decimal) -> Addr {
+            let oracle_id = app.store_code(contract_oracle());
+            let admin = app.api().addr_make(ADMIN);
+            let oracle = app
+                .instantiate_contract(oracle_id, admin.clone(), &SetRate { rate }, &[], "oracle", None)
+                .unwrap();
+            let msg = ExecuteMsg::UpdateConfig {
+                admin: None,
+                treasury: None,
+                fee_token: None,
+                fees: None,
+                default_policy: None,
+                rate_oracle: Some(oracle.to_string()),
+                trusted_tokens: None,
+            };
+            app.execute_contract(admin, cw_template_contract.addr(), &msg, &[]).unwrap();
+            oracle
+        }
+
+        fn set_reference_rate(app: &mut App, oracle: &Addr, rate: Decimal) {
+            let admin = app.api().addr_make(ADMIN);
+            app.execute_contract(admin, oracle.clone(), &SetRate { rate }, &[]).unwrap();
+        }
+
+        // Mint the standard bond from the user, paying 1% over the reference rate capped at 10%
+        fn mint_floating_bond(
+            app: &mut App,
+            cw_template_contract: &CwTemplateContract,
+            coupon_frequency: Option<u64>,
+            floor: Decimal,
+        ) -> AnyResult<AppResponse> {
+            let terms = BondTerms {
+                interest_rate: Decimal::zero(),
+                coupon_frequency,
+                floating_rate: Some(FloatingRate { spread: Decimal::percent(1), floor, cap: INTEREST_RATE }),
+                ..standard_terms()
+            };
+            mint_bond_with_terms(app, cw_template_contract, terms, REQUIRED_FUNDING)
+        }
+
+        fn rate_fixed(period: u64, rate: Decimal) -> Event {
+            Event::new("wasm-rate_fixed")
+                .add_attribute("period", period.to_string())
+                .add_attribute("rate", rate.to_string())
+        }
+
+        #[test]
+        fn coupons_follow_the_reference_rate() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            let oracle = setup_oracle(&mut app, &cw_template_contract, Decimal::percent(3));
+
+            // The first period is fixed at issuance, the issuer escrows the interest at the cap
+            let res = mint_floating_bond(&mut app, &cw_template_contract, Some(QUARTER), Decimal::percent(2)).unwrap();
+            assert!(res.has_event(&rate_fixed(1, Decimal::percent(4))));
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 1).unwrap();
+            assert_eq!(bond.interest_rate, Decimal::percent(4));
+            assert_eq!(bond.floating_rate.unwrap().cap, INTEREST_RATE);
+            let fix = ExecuteMsg::FixRate { bond_id: 1 };
+            let err = execute(&mut app, &cw_template_contract, USER, &fix, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::NoRateToFix {});
+
+            // The coupon is paid at the rate fixed for its period, the next period is capped
+            set_reference_rate(&mut app, &oracle, Decimal::percent(12));
+            app.update_block(|block| block.time = block.time.plus_seconds(QUARTER));
+            let res = execute(&mut app, &cw_template_contract, USER, &ExecuteMsg::ClaimCoupon { bond_id: 1 }, &[]).unwrap();
+            assert!(res.has_event(&rate_fixed(2, INTEREST_RATE)));
+            assert!(res.has_event(&Event::new("wasm").add_attribute("amount", "1000")));
+
+            // Anyone may fix a period that has started, the floor applies below it
+            set_reference_rate(&mut app, &oracle, Decimal::zero());
+            app.update_block(|block| block.time = block.time.plus_seconds(QUARTER));
+            let res = execute(&mut app, &cw_template_contract, USER, &fix, &[]).unwrap();
+            assert!(res.has_event(&rate_fixed(3, Decimal::percent(2))));
+            let next: NextCouponResponse = app
+                .wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::NextCoupon { bond_id: 1 })
+                .unwrap();
+            assert_eq!(next.amount_due, Uint128::new(2_500));
+
+            // The last period ended without a fixing, it keeps the rate of the period before
+            set_reference_rate(&mut app, &oracle, Decimal::percent(1));
+            app.update_block(|block| block.time = block.time.plus_seconds(2 * QUARTER));
+            let accrued: AccruedInterestResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::AccruedInterest { bond_id: 1, at_time: None },
+                )
+                .unwrap();
+            assert_eq!(accrued.accrued_interest, Uint128::new(2_500 + 500 + 500));
+
+            // Redemption pays the holder and returns the escrow left below the cap to the issuer
+            let user = app.api().addr_make(USER);
+            let balance = native_balance(&app, &user);
+            let redeem = ReceiveMsg::RedeemBond { bond_id: 1 };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 15, &redeem).unwrap();
+            assert_eq!(native_balance(&app, &user), balance + Uint128::new(REQUIRED_FUNDING - 1_000));
+            let response: RateFixingsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::RateFixings { bond_id: 1, start_after: None, limit: None },
+                )
+                .unwrap();
+            let rates: Vec<_> = response.fixings.iter().map(|fixing| fixing.rate).collect();
+            assert_eq!(rates, vec![Decimal::percent(4), INTEREST_RATE, Decimal::percent(2), Decimal::percent(2)]);
+            let issued_at = app.block_info().time.seconds() - MATURITY;
+            let last = RateFixing {
+                period: 4,
+                fixed_at: issued_at + MATURITY,
+                reference_rate: Decimal::zero(),
+                rate: Decimal::percent(2),
+                source: FixingSource::Previous,
+            };
+            assert_eq!(response.fixings[3], last);
+        }
+
+        #[test]
+        fn floating_rate_requirements() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+
+            // The reference rate is read from the configured oracle
+            let err = mint_floating_bond(&mut app, &cw_template_contract, Some(QUARTER), Decimal::zero()).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::NoRateOracle {});
+            setup_oracle(&mut app, &cw_template_contract, Decimal::percent(3));
+
+            let err = mint_floating_bond(&mut app, &cw_template_contract, None, Decimal::zero()).unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::InvalidFloatingRate { reason: "A floating-rate bond must pay coupons".to_string() }
+            );
+            let err = mint_floating_bond(&mut app, &cw_template_contract, Some(QUARTER), Decimal::percent(11))
+                .unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::InvalidFloatingRate { reason: "The floor cannot be above the cap".to_string() }
+            );
+
+            // The interest rate is set by the fixings
+            let terms = BondTerms {
+                coupon_frequency: Some(QUARTER),
+                floating_rate: Some(FloatingRate { spread: Decimal::zero(), floor: Decimal::zero(), cap: INTEREST_RATE }),
+                ..standard_terms()
+            };
+            let err = mint_bond_with_terms(&mut app, &cw_template_contract, terms, REQUIRED_FUNDING).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap().code(), "INVALID_FLOATING_RATE");
+
+            // The cap is required and bounds the escrowed interest, so it cannot be zero
+            let uncapped = r#"{"spread":"0.01","floor":"0"}"#;
+            cosmwasm_std::from_json::<FloatingRate>(uncapped).unwrap_err();
+            let terms = BondTerms {
+                interest_rate: Decimal::zero(),
+                coupon_frequency: Some(QUARTER),
+                floating_rate: Some(FloatingRate { spread: Decimal::percent(1), floor: Decimal::zero(), cap: Decimal::zero() }),
+                ..standard_terms()
+            };
+            let err = mint_bond_with_terms(&mut app, &cw_template_contract, terms, REQUIRED_FUNDING).unwrap_err();
+            assert_eq!(
+                err.downcast::<ContractError>().unwrap(),
+                ContractError::InvalidFloatingRate { reason: "The cap must be positive".to_string() }
+            );
+
+            // Fixed-rate bonds have no rate to fix
+            mint_native_bond(&mut app, &cw_template_contract);
+            let err = execute(&mut app, &cw_template_contract, USER, &ExecuteMsg::FixRate { bond_id: 1 }, &[]).unwrap_err();
+            assert_eq!(err.downcast::<ContractError>().unwrap(), ContractError::NotFloating { bond_id: 1 });
+        }
+
+        #[test]
+        fn missed_periods_and_oracle_outage_keep_the_previous_rate() {
+            let (mut app, cw_template_contract, seix_addr) = proper_instantiate();
+            let oracle = setup_oracle(&mut app, &cw_template_contract, Decimal::percent(3));
+            mint_floating_bond(&mut app, &cw_template_contract, Some(QUARTER), Decimal::zero()).unwrap();
+
+            // Period 2 ended unfixed, it keeps the 4% of period 1 whatever the oracle says now,
+            // only the running period 3 is fixed from the oracle
+            set_reference_rate(&mut app, &oracle, Decimal::percent(8));
+            app.update_block(|block| block.time = block.time.plus_seconds(2 * QUARTER));
+            let res = execute(&mut app, &cw_template_contract, USER, &ExecuteMsg::ClaimCoupon { bond_id: 1 }, &[]).unwrap();
+            assert!(res.has_event(&rate_fixed(2, Decimal::percent(4)).add_attribute("source", "previous")));
+            assert!(res.has_event(&rate_fixed(3, Decimal::percent(9)).add_attribute("source", "oracle")));
+            assert!(res.has_event(&Event::new("wasm").add_attribute("amount", "2000")));
+
+            // Without a working oracle the running period keeps the previous rate too
+            let msg = ExecuteMsg::UpdateConfig {
+                admin: None,
+                treasury: None,
+                fee_token: None,
+                fees: None,
+                default_policy: None,
+                rate_oracle: Some(seix_addr.to_string()),
+                trusted_tokens: None,
+            };
+            let admin = app.api().addr_make(ADMIN);
+            app.execute_contract(admin, cw_template_contract.addr(), &msg, &[]).unwrap();
+            app.update_block(|block| block.time = block.time.plus_seconds(QUARTER));
+            let res = execute(&mut app, &cw_template_contract, USER, &ExecuteMsg::FixRate { bond_id: 1 }, &[]).unwrap();
+            assert!(res.has_event(&rate_fixed(4, Decimal::percent(9)).add_attribute("source", "previous")));
+            assert_eq!(cw_template_contract.bond::<_, Empty>(&app, 1).unwrap().interest_rate, Decimal::percent(9));
+
+            // Redemption does not depend on the oracle either
+            app.update_block(|block| block.time = block.time.plus_seconds(QUARTER));
+            let user = app.api().addr_make(USER);
+            let redeem = ReceiveMsg::RedeemBond { bond_id: 1 };
+            send_seix(&mut app, &seix_addr, &user, &cw_template_contract, 15, &redeem).unwrap();
+            assert_eq!(cw_template_contract.bond::<_, Empty>(&app, 1).unwrap().status, BondStatus::Redeemed);
+        }
+
+        #[test]
+        fn migrate_cap_out_of_the_interest_rate() {
+            let (mut app, cw_template_contract, _) = proper_instantiate();
+            setup_oracle(&mut app, &cw_template_contract, Decimal::percent(3));
+            mint_floating_bond(&mut app, &cw_template_contract, Some(QUARTER), Decimal::zero()).unwrap();
+            {
+                // Bond 1 as 0.3.0 stored it, with the cap as its interest rate
+                let mut storage = app.contract_storage_mut(&cw_template_contract.addr());
+                let mut bond = crate::state::BONDS.load(storage.as_ref(), 1).unwrap();
+                bond.interest_rate = INTEREST_RATE;
+                let json = String::from_utf8(cosmwasm_std::to_json_vec(&bond).unwrap())
+                    .unwrap()
+                    .replace(r#","cap":"0.1""#, "");
+                storage.set(&crate::state::BONDS.key(1), json.as_bytes());
+                cw2::set_contract_version(storage.as_mut(), "crates.io:seibond-contract", "0.3.0")
+                    .unwrap();
+            }
+
+            let code_id = app.wrap().query_wasm_contract_info(cw_template_contract.addr()).unwrap().code_id;
+            let admin = app.api().addr_make(ADMIN);
+            let msg = crate::msg::MigrateMsg { legacy_denom: None, legacy_config: None };
+            app.migrate_contract(admin, cw_template_contract.addr(), &msg, code_id).unwrap();
+
+            let bond = cw_template_contract.bond::<_, Empty>(&app, 1).unwrap();
+            assert_eq!(bond.floating_rate.unwrap().cap, INTEREST_RATE);
+            assert_eq!(bond.interest_rate, Decimal::percent(4));
         }
     }
 }
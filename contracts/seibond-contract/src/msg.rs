@@ -1,28 +1,308 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-use cosmwasm_std::{Uint128, Addr};
+use cosmwasm_std::{Binary, Decimal, Uint128, Addr};
+use cw20::{Cw20ReceiveMsg, Denom};
+use cw721::msg::{NftInfoResponse, OwnerOfResponse, TokensResponse};
+use cw721::{Approval, Expiration};
+use crate::state::{
+    Auction, AuctionStatus, Bond, BondStatus, BondTerms, CallTerms, DayCount, FeeSchedule, Offering, OfferingStatus,
+    Collateral, DefaultPolicy, DefaultRecord, FloatingRate, PriceDecay, PutTerms, RateFixing, SellOrder,
+};
 
 // InstantiateMsg defines the parameters for initializing the contract
 #[cw_serde]
 pub struct InstantiateMsg {
-    pub seix_contract: String,  // The CW20 contract address for the SeiX token
+    pub seix_contract: String,  // The CW20 contract address for the SeiX token, used for fees
+    pub treasury: String,       // The address receiving platform fees
+    pub fees: FeeSchedule,      // The platform fee charged per operation
+    pub admin: Option<String>,  // The config admin, defaults to the instantiator
+    pub default_policy: Option<DefaultPolicy>, // Grace period and penalty rate, none by default
+    pub rate_oracle: Option<String>, // The contract publishing the reference rate of floating-rate bonds
+    pub trusted_tokens: Option<Vec<String>>, // CW20 tokens bonds may be minted and bought with, besides SeiX
 }
 
 // ExecuteMsg defines the different actions the contract can perform
 #[cw_serde]
 pub enum ExecuteMsg {
+    // MintBond issues a bond funded with the native coins attached to the message,
+    // the platform fee is pulled from the issuer's SeiX allowance.
+    // With a coupon frequency (in seconds) interest is paid out periodically,
+    // otherwise it is paid together with the face value at redemption.
+    // The interest rate is annual (0.05 is 5%) and accrues using the day-count
+    // convention, ACT/365 when none is given.
+    // With call terms the issuer may redeem the bond early (see NoticeCall and CallBond),
+    // with put terms the holder may (see PutBond).
+    // Collateral attached in native coins comes on top of the principal, CW20 collateral is
+    // pulled from the issuer's allowance. A collateralized bond may be escrowed only in part,
+    // the issuer repays the rest with FundBond.
+    // With a floating rate the coupon of each period is fixed from the rate oracle between
+    // the floor and the cap, the bond must pay coupons. Its interest rate is left at zero and
+    // reports the rate of the latest fixing once issued.
     MintBond {
         face_value: Uint128,
-        interest_rate: u64,
+        interest_rate: Decimal,
         maturity: u64,
+        coupon_frequency: Option<u64>,
+        day_count: Option<DayCount>,
+        call_terms: Option<CallTerms>,
+        put_terms: Option<PutTerms>,
+        collateral: Option<Collateral>,
+        floating_rate: Option<FloatingRate>,
     },
+    // TransferBond and RedeemBond pull the platform fee from the caller's SeiX allowance,
+    // the same actions can be paid for directly with a SeiX Send (see ReceiveMsg)
     TransferBond {
-        bond_id: String,
-        new_holder: Addr,
+        bond_id: u64,
+        new_holder: String,
     },
     RedeemBond {
-        bond_id: String,
+        bond_id: u64,
+    },
+    // TransferNft is the CW721 transfer of a bond, the token id is the bond id as a string.
+    // It behaves like TransferBond and may also be called by an approved spender.
+    TransferNft {
+        recipient: String,
+        token_id: String,
+    },
+    // SendNft transfers a bond to a contract and triggers its ReceiveNft hook
+    SendNft {
+        contract: String,
+        token_id: String,
+        msg: Binary,
+    },
+    // Approve allows a spender to transfer the bond until the approval expires
+    Approve {
+        spender: String,
+        token_id: String,
+        expires: Option<Expiration>,
+    },
+    // Revoke removes a previously granted approval
+    Revoke {
+        spender: String,
+        token_id: String,
+    },
+    // ClaimCoupon pays every coupon that has fallen due to the current holder
+    ClaimCoupon {
+        bond_id: u64,
+    },
+    // OpenOffering starts a primary issuance of bonds sold at `price` per unit of face value
+    // and subscribed in `denom` from `start` (defaults to now) until `end`.
+    // The mint fee is pulled from the issuer's SeiX allowance.
+    OpenOffering {
+        denom: Denom,
+        price: Decimal,
+        interest_rate: Decimal,
+        maturity: u64,
+        coupon_frequency: Option<u64>,
+        day_count: Option<DayCount>,
+        start: Option<u64>,
+        end: u64,
+        min_raise: Uint128,
+        max_raise: Uint128,
+    },
+    // Subscribe commits the native coins sent with the message to an open offering,
+    // CW20 offerings are subscribed with a Send (see ReceiveMsg)
+    Subscribe {
+        offering_id: u64,
+    },
+    // CloseOffering issues the bonds or refunds the investors once the subscription
+    // period has ended, callable by anyone. Each call settles up to `limit` subscriptions,
+    // it is called again until the offering is no longer closing.
+    CloseOffering {
+        offering_id: u64,
+        limit: Option<u32>,
+    },
+    // FundBond adds the native coins sent with the message to the escrow repaying a bond,
+    // up to what is still owed on it and until the grace period after maturity is over
+    FundBond {
+        bond_id: u64,
+    },
+    // OpenAuction starts a Dutch auction of `supply` units of a bond with the given terms,
+    // the unit price decays from `start_price` to `floor_price` between `start`
+    // (defaults to now) and `end`. The mint fee is pulled from the issuer's SeiX allowance.
+    OpenAuction {
+        denom: Denom,
+        face_value: Uint128,
+        interest_rate: Decimal,
+        maturity: u64,
+        coupon_frequency: Option<u64>,
+        day_count: Option<DayCount>,
+        supply: u64,
+        start_price: Uint128,
+        floor_price: Uint128,
+        decay: PriceDecay,
+        start: Option<u64>,
+        end: u64,
+    },
+    // Bid locks the native coins sent with the message in an open auction, the part
+    // exceeding what buys the remaining supply at the current price is refunded.
+    // CW20 auctions are bid on with a Send (see ReceiveMsg)
+    Bid {
+        auction_id: u64,
+    },
+    // CloseAuction issues the bonds at the clearing price once the auction has ended
+    // or sold out, callable by anyone. Each call settles up to `limit` bids, it is called
+    // again until the auction is no longer closing.
+    CloseAuction {
+        auction_id: u64,
+        limit: Option<u32>,
+    },
+    // ListBond offers a bond for sale at an ask price until the order expires (never by default),
+    // only callable by the holder
+    ListBond {
+        bond_id: u64,
+        denom: Denom,
+        price: Uint128,
+        expires: Option<Expiration>,
+    },
+    // BuyBond fills an order with the native coins sent with the message, which must match
+    // the ask price. The transfer fee is pulled from the buyer's SeiX allowance.
+    // Orders in a CW20 denom are filled with a Send (see ReceiveMsg)
+    BuyBond {
+        order_id: u64,
+    },
+    // CancelOrder removes an open order, only callable by the seller
+    CancelOrder {
+        order_id: u64,
+    },
+    // NoticeCall announces that a callable bond will be called once its notice period
+    // has passed, only callable by the issuer
+    NoticeCall {
+        bond_id: u64,
+    },
+    // CallBond redeems a callable bond early, paying the holder the call price plus accrued
+    // interest from escrow and returning what is left to the issuer. Bonds requiring notice
+    // can only be called from the announced date, at the price announced for it. Only callable by the issuer, who may attach
+    // native coins covering a call price above what is escrowed
+    CallBond {
+        bond_id: u64,
+    },
+    // PutBond redeems a bond early during one of its put windows, paying the holder the put
+    // price plus accrued interest from escrow. Without an amount the whole bond is put,
+    // otherwise that part of its face value and the bond carries on with the rest. Escrow
+    // beyond what the issuer still owes is returned to them. Only callable by the holder,
+    // the redeem fee is pulled from their SeiX allowance
+    PutBond {
+        bond_id: u64,
+        amount: Option<Uint128>,
+    },
+    // TopUpCollateral locks the native coins sent with the message as more collateral for
+    // a bond, CW20 collateral is topped up with a Send (see ReceiveMsg)
+    TopUpCollateral {
+        bond_id: u64,
+    },
+    // WithdrawExcessCollateral returns the collateral locked beyond what the collateral
+    // ratio requires to the issuer, only callable by the issuer
+    WithdrawExcessCollateral {
+        bond_id: u64,
+    },
+    // CancelBond withdraws a bond the issuer still holds, returning its escrow and collateral
+    CancelBond {
+        bond_id: u64,
+    },
+    // ClaimCollateral settles a bond in default, putting it in default first when it was
+    // not repaid by the end of the grace period. Only callable by the holder, who gets the
+    // escrow and a share of the collateral matching the part of the bond left unpaid,
+    // the issuer gets the rest of the collateral. An unsecured bond only pays out the escrow.
+    ClaimCollateral {
+        bond_id: u64,
+    },
+    // MarkDefault puts a bond that was not repaid by the end of the grace period in default,
+    // callable by anyone
+    MarkDefault {
+        bond_id: u64,
+    },
+    // CureDefault repays a bond in default with the native coins sent with the message,
+    // only callable by the issuer. The holder is paid in full plus penalty interest on the
+    // shortfall since maturity, anything sent beyond that is refunded and the collateral
+    // goes back to the issuer.
+    CureDefault {
+        bond_id: u64,
+    },
+    // FixRate fixes the rate of the coupon periods of a floating-rate bond that have started,
+    // the running one at the current reference rate, callable by anyone
+    FixRate {
+        bond_id: u64,
+    },
+    // UpdateConfig changes the platform configuration, only callable by the admin
+    UpdateConfig {
+        admin: Option<String>,
+        treasury: Option<String>,
+        fee_token: Option<String>,
+        fees: Option<FeeSchedule>,
+        default_policy: Option<DefaultPolicy>,
+        rate_oracle: Option<String>,
+        trusted_tokens: Option<Vec<String>>,
+    },
+    // Receive handles CW20 tokens sent to the contract with an embedded ReceiveMsg
+    Receive(Cw20ReceiveMsg),
+}
+
+// ReceiveMsg defines the actions that can be embedded in a CW20 Send.
+// When sent from the SeiX token the amount pays the platform fee for the action.
+// MintBond and BuyBond pull the fee from the sender's SeiX allowance when sent from another
+// token, which must then be one of the trusted tokens: any contract can deliver the hook
+// and claim to be sending on behalf of someone else.
+// The message is decoded once per Send, the size of the mint terms does not matter.
+#[allow(clippy::large_enum_variant)]
+#[cw_serde]
+pub enum ReceiveMsg {
+    // MintBond issues a bond funded with the CW20 tokens that were sent,
+    // after deducting the mint fee when paying in SeiX. Collateral must be in a CW20 token,
+    // it is pulled from the sender's allowance and requires a trusted token.
+    MintBond {
+        face_value: Uint128,
+        interest_rate: Decimal,
+        maturity: u64,
+        coupon_frequency: Option<u64>,
+        day_count: Option<DayCount>,
+        call_terms: Option<CallTerms>,
+        put_terms: Option<PutTerms>,
+        collateral: Option<Collateral>,
+        floating_rate: Option<FloatingRate>,
+    },
+    // TransferBond transfers a bond, paying the transfer fee in SeiX
+    TransferBond {
+        bond_id: u64,
+        new_holder: String,
+    },
+    // RedeemBond redeems a matured bond, paying the redeem fee in SeiX
+    RedeemBond {
+        bond_id: u64,
+    },
+    // Subscribe commits the CW20 tokens that were sent to an open offering
+    Subscribe {
+        offering_id: u64,
+    },
+    // FundBond adds the CW20 tokens that were sent to the escrow repaying a bond
+    FundBond {
+        bond_id: u64,
+    },
+    // Bid locks the CW20 tokens that were sent in an open auction
+    Bid {
+        auction_id: u64,
+    },
+    // CallBond calls a bond, adding the CW20 tokens that were sent to its escrow
+    CallBond {
+        bond_id: u64,
+    },
+    // PutBond puts a bond back to its issuer, paying the redeem fee in SeiX
+    PutBond {
+        bond_id: u64,
+        amount: Option<Uint128>,
+    },
+    // TopUpCollateral locks the CW20 tokens that were sent as more collateral for a bond
+    TopUpCollateral {
+        bond_id: u64,
+    },
+    // CureDefault repays a bond in default with the CW20 tokens that were sent
+    CureDefault {
+        bond_id: u64,
+    },
+    // BuyBond fills an order with the CW20 tokens that were sent. When the order is in SeiX
+    // the transfer fee is sent on top of the ask price, otherwise it is pulled from the
+    // buyer's SeiX allowance
+    BuyBond {
+        order_id: u64,
     },
 }
 
@@ -30,11 +310,169 @@ pub enum ExecuteMsg {
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
-    // GetBond returns the details of a bond given its ID
+    // GetBond returns the details of a bond given its ID, settled bonds included
     #[returns(BondResponse)]
     GetBond {
-        bond_id: String,
+        bond_id: u64,
+    },
+    // NextCoupon returns the coupon schedule position of a bond
+    #[returns(NextCouponResponse)]
+    NextCoupon {
+        bond_id: u64,
+    },
+    // AccruedInterest returns the unpaid interest and redemption amount of a bond
+    // at the given timestamp, defaulting to the current block time
+    #[returns(AccruedInterestResponse)]
+    AccruedInterest {
+        bond_id: u64,
+        at_time: Option<u64>,
+    },
+    // OwnerOf returns the CW721 owner of a bond and its approvals
+    #[returns(OwnerOfResponse)]
+    OwnerOf {
+        token_id: String,
+        include_expired: Option<bool>,
+    },
+    // NftInfo returns the CW721 metadata of a bond, with its terms as extension
+    #[returns(NftInfoResponse<BondMetadata>)]
+    NftInfo {
+        token_id: String,
+    },
+    // Tokens lists the bonds held by an owner
+    #[returns(TokensResponse)]
+    Tokens {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    // AllTokens lists every outstanding bond
+    #[returns(TokensResponse)]
+    AllTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
     },
+    // ListBonds returns all bonds ordered by bond ID
+    #[returns(BondsResponse)]
+    ListBonds {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // BondsByHolder returns the bonds currently held by an address
+    #[returns(BondsResponse)]
+    BondsByHolder {
+        holder: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // BondsByIssuer returns the bonds issued by an address
+    #[returns(BondsResponse)]
+    BondsByIssuer {
+        issuer: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // BondsMaturingBetween returns the bonds maturing between two timestamps (inclusive),
+    // ordered by maturity
+    #[returns(BondsResponse)]
+    BondsMaturingBetween {
+        from: u64,
+        to: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // BondsByStatus returns the bonds in a lifecycle status, ordered by bond ID.
+    // Settled bonds stay on record, an outstanding bond is matured from its maturity on.
+    #[returns(BondsResponse)]
+    BondsByStatus {
+        status: BondStatus,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // RateFixings returns the rates fixed for the coupon periods of a floating-rate bond,
+    // ordered by period
+    #[returns(RateFixingsResponse)]
+    RateFixings {
+        bond_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // DefaultsByIssuer returns the default history of an issuer, ordered by bond ID
+    #[returns(DefaultsResponse)]
+    DefaultsByIssuer {
+        issuer: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Offering returns the details of an offering given its ID
+    #[returns(OfferingResponse)]
+    Offering {
+        offering_id: u64,
+    },
+    // Subscription returns the amount an investor subscribed to an offering
+    #[returns(SubscriptionResponse)]
+    Subscription {
+        offering_id: u64,
+        investor: String,
+    },
+    // Auction returns the details of an auction given its ID
+    #[returns(AuctionResponse)]
+    Auction {
+        auction_id: u64,
+    },
+    // AuctionPrice returns the current price of an auction and how much of it is filled
+    #[returns(AuctionPriceResponse)]
+    AuctionPrice {
+        auction_id: u64,
+    },
+    // Order returns the details of an order given its ID
+    #[returns(OrderResponse)]
+    Order {
+        order_id: u64,
+    },
+    // OrdersByBond returns the open orders selling a bond
+    #[returns(OrdersResponse)]
+    OrdersByBond {
+        bond_id: u64,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // OrdersBySeller returns the open orders of a seller
+    #[returns(OrdersResponse)]
+    OrdersBySeller {
+        seller: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // OrdersByPrice returns the open orders in a denom from the lowest ask price,
+    // up to `max_price` (inclusive) when given
+    #[returns(OrdersResponse)]
+    OrdersByPrice {
+        denom: Denom,
+        max_price: Option<Uint128>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Config returns the current platform configuration
+    #[returns(ConfigResponse)]
+    Config {},
+}
+
+// MigrateMsg defines the parameters for migrating the contract
+#[cw_serde]
+pub struct MigrateMsg {
+    // Asset bonds minted before 0.2.0 are funded and repaid in. Those bonds were never
+    // escrowed and did not record one, so it is required when migrating them.
+    pub legacy_denom: Option<Denom>,
+    // Settings 0.1.0 did not store, required when migrating from it
+    pub legacy_config: Option<LegacyConfig>,
+}
+
+// Config of a 0.1.0 deployment, which only stored the SeiX contract address
+#[cw_serde]
+pub struct LegacyConfig {
+    pub admin: String,      // The config admin
+    pub treasury: String,   // The address receiving platform fees
+    pub fees: FeeSchedule,  // The platform fee charged per operation
 }
 
 // Define the response structure for bond details
@@ -42,8 +480,261 @@ pub enum QueryMsg {
 pub struct BondResponse {
     pub issuer: Addr,
     pub face_value: Uint128,
-    pub interest_rate: u64,
+    pub interest_rate: Decimal,
     pub maturity: u64,
     pub holder: Addr,
     pub issued_at: u64,
+    pub denom: Denom,
+    pub escrow: Uint128,
+    pub coupon_frequency: Option<u64>,
+    pub coupons_paid: u64,
+    pub approvals: Vec<Approval>,
+    pub day_count: DayCount,
+    pub call_terms: Option<CallTerms>,
+    pub call_date: Option<u64>,
+    pub put_terms: Option<PutTerms>,
+    pub collateral: Option<Collateral>,
+    pub status: BondStatus,
+    pub floating_rate: Option<FloatingRate>,
+}
+
+impl From<Bond> for BondResponse {
+    fn from(bond: Bond) -> Self {
+        BondResponse {
+            issuer: bond.issuer,
+            face_value: bond.face_value,
+            interest_rate: bond.interest_rate,
+            maturity: bond.maturity,
+            holder: bond.holder,
+            issued_at: bond.issued_at,
+            denom: bond.denom,
+            escrow: bond.escrow,
+            coupon_frequency: bond.coupon_frequency,
+            coupons_paid: bond.coupons_paid,
+            approvals: bond.approvals,
+            day_count: bond.day_count,
+            call_terms: bond.call_terms,
+            call_date: bond.call_date,
+            put_terms: bond.put_terms,
+            collateral: bond.collateral,
+            status: bond.status,
+            floating_rate: bond.floating_rate,
+        }
+    }
+}
+
+// Define a bond together with its ID, as returned by the listing queries
+#[cw_serde]
+pub struct BondInfo {
+    pub bond_id: u64,
+    pub bond: BondResponse,
+}
+
+// Define the response structure for bond listings
+#[cw_serde]
+pub struct BondsResponse {
+    pub bonds: Vec<BondInfo>,
+}
+
+// Define the bond terms exposed as CW721 extension metadata
+#[cw_serde]
+pub struct BondMetadata {
+    pub issuer: Addr,
+    pub face_value: Uint128,
+    pub interest_rate: Decimal,
+    pub maturity: u64,
+    pub issued_at: u64,
+    pub denom: Denom,
+    pub coupon_frequency: Option<u64>,
+    pub day_count: DayCount,
+    pub call_terms: Option<CallTerms>,
+    pub put_terms: Option<PutTerms>,
+    pub collateral: Option<Collateral>,
+    pub floating_rate: Option<FloatingRate>,
+}
+
+// Define the response structure for the coupon schedule of a bond
+#[cw_serde]
+pub struct NextCouponResponse {
+    pub next_coupon_date: Option<u64>,  // When the next unpaid coupon falls due, if any remain
+    pub coupons_due: u64,               // Coupons that have fallen due and are not yet paid
+    pub amount_due: Uint128,            // Amount claimable now with ClaimCoupon
+}
+
+// Rounding applied when interest is converted to whole units of the bond's denomination
+#[cw_serde]
+pub enum RoundingPolicy {
+    // Round down, any fraction of a unit is never paid out
+    Floor,
+}
+
+// Define the response structure for the accrued interest of a bond
+#[cw_serde]
+pub struct AccruedInterestResponse {
+    pub at_time: u64,                   // Timestamp the amounts are computed at
+    pub day_count: DayCount,            // Convention used to accrue interest
+    pub accrued_interest: Uint128,      // Interest accrued by `at_time` and not yet paid as coupons
+    pub redemption_amount: Uint128,     // Face value plus the accrued interest
+    pub rounding: RoundingPolicy,       // Rounding applied to the amounts
+}
+
+// Define the response structure for offering details
+#[cw_serde]
+pub struct OfferingResponse {
+    pub issuer: Addr,
+    pub denom: Denom,
+    pub price: Decimal,
+    pub interest_rate: Decimal,
+    pub maturity: u64,
+    pub coupon_frequency: Option<u64>,
+    pub day_count: DayCount,
+    pub start: u64,
+    pub end: u64,
+    pub min_raise: Uint128,
+    pub max_raise: Uint128,
+    pub subscribed: Uint128,
+    pub raised: Uint128,
+    pub status: OfferingStatus,
+}
+
+impl From<Offering> for OfferingResponse {
+    fn from(offering: Offering) -> Self {
+        OfferingResponse {
+            issuer: offering.issuer,
+            denom: offering.denom,
+            price: offering.price,
+            interest_rate: offering.interest_rate,
+            maturity: offering.maturity,
+            coupon_frequency: offering.coupon_frequency,
+            day_count: offering.day_count,
+            start: offering.start,
+            end: offering.end,
+            min_raise: offering.min_raise,
+            max_raise: offering.max_raise,
+            subscribed: offering.subscribed,
+            raised: offering.raised,
+            status: offering.status,
+        }
+    }
+}
+
+// Define the response structure for the subscription of an investor
+#[cw_serde]
+pub struct SubscriptionResponse {
+    pub amount: Uint128,
+}
+
+// Define the response structure for auction details
+#[cw_serde]
+pub struct AuctionResponse {
+    pub issuer: Addr,
+    pub denom: Denom,
+    pub terms: BondTerms,
+    pub supply: u64,
+    pub start_price: Uint128,
+    pub floor_price: Uint128,
+    pub decay: PriceDecay,
+    pub start: u64,
+    pub end: u64,
+    pub committed: Uint128,
+    pub clearing_price: Option<Uint128>,
+    pub status: AuctionStatus,
+}
+
+impl From<Auction> for AuctionResponse {
+    fn from(auction: Auction) -> Self {
+        AuctionResponse {
+            issuer: auction.issuer,
+            denom: auction.denom,
+            terms: auction.terms,
+            supply: auction.supply,
+            start_price: auction.start_price,
+            floor_price: auction.floor_price,
+            decay: auction.decay,
+            start: auction.start,
+            end: auction.end,
+            committed: auction.committed,
+            clearing_price: auction.clearing_price,
+            status: auction.status,
+        }
+    }
+}
+
+// Define the response structure for the current state of an auction
+#[cw_serde]
+pub struct AuctionPriceResponse {
+    pub price: Uint128,                 // Price a unit would clear at now
+    pub committed: Uint128,             // Total funds locked by bidders
+    pub filled: u64,                    // Units the locked funds buy at `price`
+    pub supply: u64,                    // Units for sale
+    pub sold_out: bool,                 // Whether the locked funds buy the whole supply
+}
+
+// Define the response structure for order details
+#[cw_serde]
+pub struct OrderResponse {
+    pub order_id: u64,
+    pub bond_id: u64,
+    pub seller: Addr,
+    pub denom: Denom,
+    pub price: Uint128,
+    pub expires: Expiration,
+}
+
+impl OrderResponse {
+    pub fn new(order_id: u64, order: SellOrder) -> Self {
+        OrderResponse {
+            order_id,
+            bond_id: order.bond_id,
+            seller: order.seller,
+            denom: order.denom,
+            price: order.price,
+            expires: order.expires,
+        }
+    }
+}
+
+// Define the response structure for order listings
+#[cw_serde]
+pub struct OrdersResponse {
+    pub orders: Vec<OrderResponse>,
+}
+
+// Define the response structure for the platform configuration
+#[cw_serde]
+pub struct ConfigResponse {
+    pub admin: Addr,
+    pub treasury: Addr,
+    pub fee_token: Addr,
+    pub fees: FeeSchedule,
+    pub default_policy: DefaultPolicy,
+    pub rate_oracle: Option<Addr>,
+    pub trusted_tokens: Vec<Addr>,
+}
+
+// Define the response structure for the default history of an issuer
+#[cw_serde]
+pub struct DefaultsResponse {
+    pub defaults: Vec<DefaultRecord>,
+}
+
+// Define the response structure for the rate fixings of a floating-rate bond
+#[cw_serde]
+pub struct RateFixingsResponse {
+    pub fixings: Vec<RateFixing>,
+}
+
+// OracleQueryMsg is the query the rate oracle answers for floating-rate bonds
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum OracleQueryMsg {
+    // ReferenceRate returns the current annual reference rate
+    #[returns(ReferenceRateResponse)]
+    ReferenceRate {},
+}
+
+// Define the response structure of the rate oracle
+#[cw_serde]
+pub struct ReferenceRateResponse {
+    pub rate: Decimal,                  // Annual rate (0.05 is 5%)
 }
@@ -1,24 +1,560 @@
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Denom;
+use cw721::{Approval, Expiration};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 
 // Define the structure for each Bond
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Bond {
     pub issuer: Addr,           // Address of the issuer
     pub face_value: Uint128,     // Face value of the bond
-    pub interest_rate: u64,      // Interest rate (in percentage)
+    #[serde(deserialize_with = "percent_or_decimal")]
+    pub interest_rate: Decimal,  // Annual interest rate (0.05 is 5%), of the latest fixing for a floating-rate bond
     pub maturity: u64,           // Maturity period (in seconds or days)
     pub holder: Addr,            // Current holder of the bond
     pub issued_at: u64,          // When the bond was issued (timestamp)
+    pub denom: Denom,            // Asset the bond is funded and repaid in
+    pub escrow: Uint128,         // Funds held by the contract to settle the bond
+    pub coupon_frequency: Option<u64>, // Seconds between coupon payments, if any
+    pub coupons_paid: u64,       // Number of coupon periods already paid out
+    pub approvals: Vec<Approval>, // Spenders allowed to transfer the bond NFT
+    #[serde(default)]
+    pub day_count: DayCount,     // Convention used to accrue interest
+    #[serde(default)]
+    pub call_terms: Option<CallTerms>, // Provisions allowing the issuer to redeem early
+    #[serde(default)]
+    pub call_date: Option<u64>,  // When the bond will be called, once notice was given
+    #[serde(default)]
+    pub put_terms: Option<PutTerms>, // Provisions allowing the holder to redeem early
+    #[serde(default)]
+    pub collateral: Option<Collateral>, // Collateral locked by the issuer against the bond
+    #[serde(default)]
+    pub status: BondStatus,      // Stage of the bond in its lifecycle
+    #[serde(default, deserialize_with = "floating_rate_without_cap")]
+    pub floating_rate: Option<FloatingRate>, // Coupon set from the reference rate, for a floating-rate bond
 }
 
-// Store all bonds as a map of bond ID -> Bond struct
-pub const BONDS: Map<String, Bond> = Map::new("bonds");
+// Bonds stored by version 0.2.0 may still hold their rate in whole percent, read those as
+// a Decimal. `migrate` rewrites them in the current format.
+fn percent_or_decimal<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Decimal, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum StoredRate {
+        Percent(u64),
+        Decimal(Decimal),
+    }
+    Ok(match StoredRate::deserialize(deserializer)? {
+        StoredRate::Percent(percent) => Decimal::percent(percent),
+        StoredRate::Decimal(rate) => rate,
+    })
+}
+
+// Floating-rate bonds stored before version 0.4.0 had no cap, it was their interest rate.
+// Read those with a zero cap, `migrate` sets it from the interest rate.
+fn floating_rate_without_cap<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<FloatingRate>, D::Error> {
+    #[derive(Deserialize)]
+    struct StoredFloatingRate {
+        spread: Decimal,
+        floor: Decimal,
+        #[serde(default)]
+        cap: Decimal,
+    }
+    Ok(Option::<StoredFloatingRate>::deserialize(deserializer)?.map(|stored| FloatingRate {
+        spread: stored.spread,
+        floor: stored.floor,
+        cap: stored.cap,
+    }))
+}
+
+impl Bond {
+    // Status of the bond at `now`. Outstanding bonds are not updated when they mature,
+    // they are reported as matured from their maturity on.
+    pub fn status_at(&self, now: u64) -> BondStatus {
+        match self.status {
+            BondStatus::Pending | BondStatus::Active if now >= self.issued_at + self.maturity => {
+                BondStatus::Matured
+            }
+            status => status,
+        }
+    }
+}
+
+// Lifecycle of a bond. Settled bonds are kept on record with the status they ended in.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BondStatus {
+    // Issued, but the escrow does not cover the face value and interest yet
+    Pending,
+    // Issued and fully escrowed
+    #[default]
+    Active,
+    // Past maturity and not settled yet
+    Matured,
+    // Repaid to the holder at maturity, or put back to the issuer in full
+    Redeemed,
+    // Called early by the issuer
+    Called,
+    // Not repaid by the issuer
+    Defaulted,
+    // Withdrawn by the issuer before it was sold
+    Cancelled,
+}
+
+impl BondStatus {
+    // Whether the bond is still outstanding and open to operations
+    pub fn is_outstanding(self) -> bool {
+        matches!(self, BondStatus::Pending | BondStatus::Active | BondStatus::Matured)
+    }
+
+    // Whether a bond may move from this status to `next`. Maturity is only ever derived
+    // from the time (see `Bond::status_at`), so no status moves to `Matured`. A defaulted
+    // bond is redeemed when the issuer cures the default.
+    pub fn can_become(self, next: BondStatus) -> bool {
+        use BondStatus::*;
+        matches!(
+            (self, next),
+            (Pending, Active)
+                | (Pending | Active, Called | Cancelled)
+                | (Pending | Active | Matured, Redeemed | Defaulted)
+                | (Defaulted, Redeemed)
+        )
+    }
+}
+
+impl fmt::Display for BondStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            BondStatus::Pending => "pending",
+            BondStatus::Active => "active",
+            BondStatus::Matured => "matured",
+            BondStatus::Redeemed => "redeemed",
+            BondStatus::Called => "called",
+            BondStatus::Defaulted => "defaulted",
+            BondStatus::Cancelled => "cancelled",
+        };
+        f.write_str(name)
+    }
+}
+
+// Collateral locked by the issuer against a bond. When the bond defaults the holder may
+// claim it, otherwise it goes back to the issuer once the bond is settled.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Collateral {
+    pub denom: Denom,            // Asset locked as collateral
+    pub amount: Uint128,         // Amount currently locked
+    pub ratio: Decimal,          // Collateral required per unit of face value (1.5 is 150%)
+}
+
+// Provisions allowing the issuer to call a bond before maturity. The holder is paid the
+// call price plus the interest accrued and not yet paid as coupons.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct CallTerms {
+    pub protection: u64,         // Seconds after issuance during which the bond cannot be called
+    pub schedule: Vec<RedemptionPrice>, // Call prices by time since issuance, in ascending order
+    pub notice: u64,             // Seconds of notice the holder gets before the bond is called
+}
+
+// Provisions allowing the holder to redeem a bond from escrow before maturity, during one
+// of the exercise windows. The holder is paid the put price plus the interest accrued and
+// not yet paid as coupons, a put price below 1 is a discount on the face value.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PutTerms {
+    pub windows: Vec<PutWindow>, // Periods in which the bond can be put, in ascending order
+    pub schedule: Vec<RedemptionPrice>, // Put prices by time since issuance, in ascending order
+}
+
+// Period in the life of a bond during which the holder may put it
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PutWindow {
+    pub start: u64,              // Seconds after issuance at which the window opens
+    pub end: u64,                // Seconds after issuance at which the window closes
+}
+
+// Call or put price applying from a point in the life of a bond until the next schedule entry
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RedemptionPrice {
+    pub from: u64,               // Seconds after issuance from which this price applies
+    pub price: Decimal,          // Fraction of the face value paid (1.02 is 102%)
+}
+
+// Coupon of a floating-rate bond. The rate of each coupon period is fixed from the reference
+// rate published by the rate oracle, plus the spread and kept between the floor and the cap.
+// The issuer escrows the interest at the cap, the escrow left over at redemption goes back
+// to them.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FloatingRate {
+    pub spread: Decimal,         // Added to the reference rate (0.01 is 100 basis points)
+    pub floor: Decimal,          // Lowest rate a period can be fixed at
+    pub cap: Decimal,            // Highest rate a period can be fixed at
+}
+
+impl FloatingRate {
+    // Rate paid over a period for the reference rate it was fixed at
+    pub fn rate(&self, reference_rate: Decimal) -> Decimal {
+        (reference_rate + self.spread).max(self.floor).min(self.cap)
+    }
+}
+
+// Rate fixed for one coupon period of a floating-rate bond, kept so that its coupons can be
+// recomputed. A period is fixed from the oracle the first time it is needed while it runs,
+// anyone may call FixRate when it starts so that it is not fixed later at a chosen moment.
+// A period that ended without a fixing, or that could not read the oracle, keeps the rate
+// of the period before it, or the cap for the first period.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateFixing {
+    pub period: u64,             // Coupon period, counted from 1
+    pub fixed_at: u64,           // When the rate was fixed
+    pub reference_rate: Decimal, // Rate read from the oracle, or carried over with the rate
+    pub rate: Decimal,           // Annual rate the period accrues at
+    #[serde(default)]
+    pub source: FixingSource,    // Where the rate came from
+}
+
+// Source of the rate of a coupon period
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FixingSource {
+    // Read from the rate oracle while the period was running
+    #[default]
+    Oracle,
+    // Carried over from the fixing of the previous period
+    Previous,
+    // Set at the cap, for a first period without an oracle rate
+    Cap,
+}
+
+impl fmt::Display for FixingSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            FixingSource::Oracle => "oracle",
+            FixingSource::Previous => "previous",
+            FixingSource::Cap => "cap",
+        };
+        f.write_str(name)
+    }
+}
+
+// Store the rate fixings as a map of (bond ID, period) -> RateFixing
+pub const RATE_FIXINGS: Map<(u64, u64), RateFixing> = Map::new("rate_fixings");
+
+// Day-count conventions used to turn an accrual period into a fraction of a year
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DayCount {
+    // Actual elapsed time over a 365 day year
+    #[default]
+    Act365,
+    // Actual elapsed time over a 360 day year
+    Act360,
+    // Whole calendar days counted as 30 day months over a 360 day year (US bond basis)
+    Thirty360,
+}
+
+// Define the terms a new bond is issued with
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BondTerms {
+    pub face_value: Uint128,
+    pub interest_rate: Decimal,
+    pub maturity: u64,
+    pub coupon_frequency: Option<u64>,
+    pub day_count: DayCount,
+    #[serde(default)]
+    pub call_terms: Option<CallTerms>,
+    #[serde(default)]
+    pub put_terms: Option<PutTerms>,
+    #[serde(default)]
+    pub collateral: Option<Collateral>,
+    #[serde(default)]
+    pub floating_rate: Option<FloatingRate>,
+}
+
+// Secondary indexes over the bonds, each entry points back to the bond ID of type `K`
+pub struct BondIndexes<'a, K> {
+    pub holder: MultiIndex<'a, Addr, Bond, K>,
+    pub issuer: MultiIndex<'a, Addr, Bond, K>,
+    pub maturity: MultiIndex<'a, u64, Bond, K>, // Timestamp at which the bond matures
+    pub status: MultiIndex<'a, String, Bond, K>, // Stored status, see `BondStatus::fmt`
+}
+
+impl<K> IndexList<Bond> for BondIndexes<'_, K> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Bond>> + '_> {
+        let v: Vec<&dyn Index<Bond>> = vec![&self.holder, &self.issuer, &self.maturity, &self.status];
+        Box::new(v.into_iter())
+    }
+}
+
+const fn bond_indexes<'a, K>() -> BondIndexes<'a, K> {
+    BondIndexes {
+        holder: MultiIndex::new(|_, bond| bond.holder.clone(), "bonds", "bonds__holder"),
+        issuer: MultiIndex::new(|_, bond| bond.issuer.clone(), "bonds", "bonds__issuer"),
+        maturity: MultiIndex::new(|_, bond| bond.issued_at + bond.maturity, "bonds", "bonds__maturity"),
+        status: MultiIndex::new(|_, bond| bond.status.to_string(), "bonds", "bonds__status"),
+    }
+}
+
+// Store all bonds as a map of bond ID -> Bond struct, indexed by holder, issuer, maturity and status
+pub const BONDS: IndexedMap<u64, Bond, BondIndexes<u64>> = IndexedMap::new("bonds", bond_indexes());
+
+// Bond as stored before version 0.2.0, when bonds were minted without any escrow
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyBond {
+    pub issuer: Addr,           // Address of the issuer
+    pub face_value: Uint128,     // Face value of the bond
+    pub interest_rate: u64,      // Annual interest rate in whole percent
+    pub maturity: u64,           // Maturity period (in seconds or days)
+    pub holder: Addr,            // Current holder of the bond
+    pub issued_at: u64,          // When the bond was issued (timestamp)
+}
+
+impl LegacyBond {
+    // Convert to the current schema. Legacy bonds hold no escrow, so they come out
+    // pending until the issuer funds them in `denom`.
+    pub fn upgrade(self, denom: Denom) -> Bond {
+        Bond {
+            issuer: self.issuer,
+            face_value: self.face_value,
+            interest_rate: Decimal::percent(self.interest_rate),
+            maturity: self.maturity,
+            holder: self.holder,
+            issued_at: self.issued_at,
+            denom,
+            escrow: Uint128::zero(),
+            coupon_frequency: None,
+            coupons_paid: 0,
+            approvals: vec![],
+            day_count: DayCount::default(),
+            call_terms: None,
+            call_date: None,
+            put_terms: None,
+            collateral: None,
+            status: BondStatus::Pending,
+            floating_rate: None,
+        }
+    }
+}
+
+// Bonds as stored before version 0.2.0, keyed by the decimal string of their ID and
+// without indexes. Only read by `migrate` to move them over to `BONDS`.
+pub const LEGACY_BONDS: Map<String, LegacyBond> = Map::new("bonds");
+
+// Address of the SeiX contract as stored before version 0.2.0, which became the fee token
+// of `CONFIG`. Only read by `migrate`.
+pub const LEGACY_SEIX_CONTRACT: Item<Addr> = Item::new("seix_contract");
 
 // Store a counter to track bond IDs
 pub const BOND_COUNT: Item<u64> = Item::new("bond_count");
 
-// Store the SeiX contract address
-pub const SEIX_CONTRACT: Item<Addr> = Item::new("seix_contract");
+// Primary issuance of bonds to investors. Investors subscribe during the subscription
+// period, at close each is allocated a bond pro-rata to their subscription (accepting at
+// most `max_raise` overall) and the proceeds go to the issuer, or everyone is refunded
+// when less than `min_raise` was subscribed.
+// Bonds issued this way are not escrowed upfront, the issuer repays them with FundBond.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Offering {
+    pub issuer: Addr,            // Address raising the funds and owing the bonds
+    pub denom: Denom,            // Asset subscriptions are paid in and the bonds repaid in
+    pub price: Decimal,          // Price paid per unit of face value (0.98 is a 2% discount)
+    pub interest_rate: Decimal,  // Annual interest rate of the bonds (0.05 is 5%)
+    pub maturity: u64,           // Maturity period of the bonds, counted from the close
+    pub coupon_frequency: Option<u64>, // Seconds between coupon payments, if any
+    pub day_count: DayCount,     // Convention used to accrue interest
+    pub start: u64,              // Subscriptions open at this timestamp
+    pub end: u64,                // Subscriptions close at this timestamp
+    pub min_raise: Uint128,      // Below this amount every subscription is refunded
+    pub max_raise: Uint128,      // At most this amount is accepted, the rest is refunded
+    pub subscribed: Uint128,     // Total amount subscribed so far
+    pub raised: Uint128,         // Amount paid to the issuer at close
+    pub status: OfferingStatus,
+    #[serde(default)]
+    pub closed_through: Option<Addr>, // Last investor settled while the offering is closing
+    #[serde(default)]
+    pub closed_at: Option<u64>,  // When closing started, every bond is issued at this timestamp
+}
+
+// Lifecycle of an offering
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum OfferingStatus {
+    // Accepting subscriptions during the subscription period
+    Open,
+    // Ended, with subscriptions settled a page at a time by CloseOffering
+    Closing,
+    // Closed with bonds issued to the investors
+    Settled,
+    // Closed below the minimum raise, every subscription was refunded
+    Refunded,
+}
+
+// Store all offerings as a map of offering ID -> Offering struct
+pub const OFFERINGS: Map<u64, Offering> = Map::new("offerings");
+
+// Store a counter to track offering IDs
+pub const OFFERING_COUNT: Item<u64> = Item::new("offering_count");
+
+// Amount subscribed by each investor, keyed by (offering ID, investor)
+pub const SUBSCRIPTIONS: Map<(u64, &Addr), Uint128> = Map::new("subscriptions");
+
+// Dutch auction of bond units. The unit price decays from `start_price` to `floor_price`
+// over the bidding window, bidders lock funds and every winner pays the same clearing price:
+// the current price, or the price at which the locked funds buy the whole supply once the
+// auction sells out. Like offered bonds, the bonds issued are repaid by the issuer with FundBond.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Auction {
+    pub issuer: Addr,            // Address raising the funds and owing the bonds
+    pub denom: Denom,            // Asset bids are paid in and the bonds repaid in
+    pub terms: BondTerms,        // Terms of one unit, winners get a single bond for all their units
+    pub supply: u64,             // Number of units for sale
+    pub start_price: Uint128,    // Price of a unit when bidding opens
+    pub floor_price: Uint128,    // Price of a unit when bidding closes
+    pub decay: PriceDecay,       // How the price moves from the start price to the floor
+    pub start: u64,              // Bidding opens at this timestamp
+    pub end: u64,                // Bidding closes at this timestamp
+    pub committed: Uint128,      // Total funds locked by bidders
+    pub clearing_price: Option<Uint128>, // Price every winner pays, fixed when closing starts
+    pub status: AuctionStatus,
+    #[serde(default)]
+    pub closed_through: Option<Addr>, // Last bidder settled while the auction is closing
+    #[serde(default)]
+    pub closed_at: Option<u64>,  // When closing started, every bond is issued at this timestamp
+}
+
+// Shape of the price decay over the bidding window
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceDecay {
+    // The price falls continuously
+    Linear,
+    // The price falls at the end of every `interval` seconds, following the linear decay
+    Stepwise { interval: u64 },
+}
+
+// Lifecycle of an auction
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuctionStatus {
+    // Accepting bids until the window ends or the supply sells out
+    Open,
+    // Ended, with bids settled a page at a time by CloseAuction
+    Closing,
+    // Closed with bonds issued to the winners at the clearing price
+    Settled,
+}
+
+// Store all auctions as a map of auction ID -> Auction struct
+pub const AUCTIONS: Map<u64, Auction> = Map::new("auctions");
+
+// Store a counter to track auction IDs
+pub const AUCTION_COUNT: Item<u64> = Item::new("auction_count");
+
+// Funds locked by each bidder, keyed by (auction ID, bidder)
+pub const BIDS: Map<(u64, &Addr), Uint128> = Map::new("bids");
+
+// Limit order selling a bond on the secondary market. The seller keeps the bond until the
+// order is filled, any transfer or redemption of the bond cancels its open orders.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SellOrder {
+    pub bond_id: u64,            // Bond for sale
+    pub seller: Addr,            // Holder of the bond when it was listed
+    pub denom: Denom,            // Asset the bond is sold for
+    pub price: Uint128,          // Ask price for the whole bond
+    pub expires: Expiration,     // The order can no longer be filled after this
+}
+
+// Secondary indexes over the open orders, each entry points back to the order ID
+pub struct OrderIndexes<'a> {
+    pub bond: MultiIndex<'a, u64, SellOrder, u64>,
+    pub seller: MultiIndex<'a, Addr, SellOrder, u64>,
+    pub price: MultiIndex<'a, (String, u128), SellOrder, u64>, // Ask price within each denom
+}
+
+impl IndexList<SellOrder> for OrderIndexes<'_> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<SellOrder>> + '_> {
+        let v: Vec<&dyn Index<SellOrder>> = vec![&self.bond, &self.seller, &self.price];
+        Box::new(v.into_iter())
+    }
+}
+
+// Key identifying a denom in the price index
+pub fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => format!("native:{}", denom),
+        Denom::Cw20(contract_addr) => format!("cw20:{}", contract_addr),
+    }
+}
+
+// Store the open orders as a map of order ID -> SellOrder struct, indexed by bond, seller and price
+pub const ORDERS: IndexedMap<u64, SellOrder, OrderIndexes> = IndexedMap::new(
+    "orders",
+    OrderIndexes {
+        bond: MultiIndex::new(|_, order| order.bond_id, "orders", "orders__bond"),
+        seller: MultiIndex::new(|_, order| order.seller.clone(), "orders", "orders__seller"),
+        price: MultiIndex::new(
+            |_, order| (denom_key(&order.denom), order.price.u128()),
+            "orders",
+            "orders__price",
+        ),
+    },
+);
+
+// Store a counter to track order IDs
+pub const ORDER_COUNT: Item<u64> = Item::new("order_count");
+
+// Platform fees charged in the fee token for each bond operation
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeSchedule {
+    pub mint: Uint128,
+    pub transfer: Uint128,
+    pub redeem: Uint128,
+}
+
+// How bonds that are not repaid at maturity are handled
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema)]
+pub struct DefaultPolicy {
+    pub grace_period: u64,       // Seconds after maturity the issuer has to repay a bond in full
+    pub penalty_rate: Decimal,   // Annual rate charged on the shortfall of a defaulted bond
+}
+
+// Deployment-wide configuration, managed by the admin
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Config {
+    pub admin: Addr,             // Address allowed to update the config
+    pub treasury: Addr,          // Recipient of the platform fees
+    pub fee_token: Addr,         // CW20 contract fees are paid in (SeiX)
+    pub fees: FeeSchedule,       // Fee charged per operation
+    #[serde(default)]
+    pub default_policy: DefaultPolicy, // Grace period and penalty for late repayment
+    #[serde(default)]
+    pub rate_oracle: Option<Addr>, // Contract publishing the reference rate of floating-rate bonds
+    #[serde(default)]
+    pub trusted_tokens: Vec<Addr>, // CW20 tokens besides the fee token whose Send hooks may pull the sender's allowance
+}
+
+// Store the contract configuration
+pub const CONFIG: Item<Config> = Item::new("config");
+
+// Default of an issuer on a bond, part of the issuer's default history
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DefaultRecord {
+    pub bond_id: u64,
+    pub defaulted_at: u64,       // When the bond was put in default
+    pub shortfall: Uint128,      // Part of the face value and interest the escrow did not cover
+    pub resolution: Option<DefaultResolution>, // How the default ended, once it has
+}
+
+// How a default ended
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DefaultResolution {
+    // The issuer repaid the holder in full, with penalty interest
+    Cured { at: u64, penalty: Uint128 },
+    // The holder claimed the escrow and their share of any collateral instead
+    Liquidated { at: u64, claimed: Uint128 },
+}
+
+// Store the default history as a map of (issuer, bond ID) -> DefaultRecord
+pub const DEFAULTS: Map<(&Addr, u64), DefaultRecord> = Map::new("defaults");
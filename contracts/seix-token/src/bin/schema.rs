@@ -1,11 +1,13 @@
 use cosmwasm_schema::write_api;
+use cw20::Cw20ExecuteMsg;
 
-use seix_token::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use seix_token::msg::{InstantiateMsg, MigrateMsg, QueryMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
-        execute: ExecuteMsg,
+        execute: Cw20ExecuteMsg,
         query: QueryMsg,
+        migrate: MigrateMsg,
     }
 }
@@ -1,16 +1,34 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
-use cosmwasm_std::{to_json_binary, Addr, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Binary, Uint128, StdError};
-use cw2::set_contract_version;
-use cw20::{Cw20ExecuteMsg, BalanceResponse, TokenInfoResponse, Cw20QueryMsg};
+use cosmwasm_std::{to_json_binary, Addr, Api, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage, Binary, Uint128, StdError};
+use cw2::{get_contract_version, set_contract_version};
+use cw20::{
+    AllAccountsResponse, AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceInfo, AllowanceResponse,
+    BalanceResponse, Cw20ExecuteMsg, Cw20ReceiveMsg, DownloadLogoResponse, EmbeddedLogo,
+    Expiration, Logo, LogoInfo, MarketingInfoResponse, MinterResponse, SpenderAllowanceInfo,
+    TokenInfoResponse,
+};
+use cw_storage_plus::Bound;
 use crate::error::ContractError;
-use crate::msg::InstantiateMsg;
-use crate::state::{BALANCES, TOKEN_INFO, MINTER, TokenInfo};
+use crate::msg::{HolderCountResponse, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{
+    ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, HOLDER_COUNT, LEGACY_MINTER, LOGO, MARKETING_INFO,
+    TOKEN_INFO, MINTER, MinterData, TokenInfo,
+};
+use semver::Version;
+use std::collections::BTreeSet;
 
 // version info for migration
 const CONTRACT_NAME: &str = "crates.io:seix-token";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// Maximum size of a logo embedded on chain
+const LOGO_SIZE_CAP: usize = 5 * 1024;
+
+// Pagination limits for listing queries
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 // Instantiate the contract
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -19,10 +37,29 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
+    validate_token_info(&msg)?;
     let total_supply = Uint128::new(msg.initial_supply);
-    
-    // Set initial balance to the instantiator
-    BALANCES.save(deps.storage, &info.sender, &total_supply)?;
+
+    // Distribute the initial supply, each address may appear only once
+    HOLDER_COUNT.save(deps.storage, &0)?;
+    let mut distributed = Uint128::zero();
+    let mut recipients = BTreeSet::new();
+    for coin in msg.initial_balances {
+        let address = validate_addr(deps.api, "initial_balances", &coin.address)?;
+        if !recipients.insert(address.clone()) {
+            return Err(ContractError::DuplicateInitialBalance {
+                address: address.to_string(),
+            });
+        }
+        distributed = distributed.checked_add(coin.amount).map_err(StdError::from)?;
+        add_balance(deps.storage, &address, coin.amount)?;
+    }
+    if distributed != total_supply {
+        return Err(ContractError::InvalidInitialBalances {
+            distributed,
+            total_supply,
+        });
+    }
 
     // Initialize token info
     let token_info = TokenInfo {
@@ -33,9 +70,38 @@ pub fn instantiate(
     };
     TOKEN_INFO.save(deps.storage, &token_info)?;
 
-    // If a minter is provided, store it
-    if let Some(minter) = msg.minter {
-        MINTER.save(deps.storage, &Addr::unchecked(minter))?;
+    // If a minter is provided, store it together with the supply cap
+    if let Some(mint) = msg.mint {
+        if let Some(cap) = mint.cap.filter(|cap| total_supply > *cap) {
+            return Err(ContractError::CapExceeded { total_supply, cap });
+        }
+        let minter = MinterData {
+            minter: validate_addr(deps.api, "minter", &mint.minter)?,
+            cap: mint.cap,
+        };
+        MINTER.save(deps.storage, &minter)?;
+    }
+
+    // Store the marketing details and logo, if provided
+    if let Some(marketing) = msg.marketing {
+        let logo = match marketing.logo {
+            Some(logo) => {
+                verify_logo(&logo)?;
+                LOGO.save(deps.storage, &logo)?;
+                Some(logo_info(&logo))
+            }
+            None => None,
+        };
+        let marketing_info = MarketingInfoResponse {
+            project: marketing.project,
+            description: marketing.description,
+            marketing: marketing
+                .marketing
+                .map(|addr| validate_addr(deps.api, "marketing", &addr))
+                .transpose()?,
+            logo,
+        };
+        MARKETING_INFO.save(deps.storage, &marketing_info)?;
     }
 
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
@@ -46,19 +112,116 @@ pub fn instantiate(
         .add_attribute("total_supply", total_supply.to_string()))
 }
 
+// Check the token name, symbol and decimals before instantiating
+fn validate_token_info(msg: &InstantiateMsg) -> Result<(), ContractError> {
+    if !(3..=50).contains(&msg.name.len()) {
+        return Err(ContractError::InvalidName {});
+    }
+    let valid_symbol = (3..=12).contains(&msg.symbol.len())
+        && msg.symbol.bytes().all(|byte| byte.is_ascii_alphabetic() || byte == b'-');
+    if !valid_symbol {
+        return Err(ContractError::InvalidSymbol {});
+    }
+    if msg.decimals > 18 {
+        return Err(ContractError::InvalidDecimals {});
+    }
+    Ok(())
+}
+
+// Migrate the contract to this version
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::WrongContract {
+            contract: stored.contract,
+        });
+    }
+
+    // Refuse downgrades, migrating to the same version is a no-op
+    let stored_version = parse_version(&stored.version)?;
+    if stored_version > parse_version(CONTRACT_VERSION)? {
+        return Err(ContractError::Downgrade {
+            from: stored.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // Before 0.2.0 the minter was a bare address, keep it without a cap.
+    // Empty balances were kept around and holders were not counted.
+    if stored_version < Version::new(0, 2, 0) {
+        if let Some(minter) = LEGACY_MINTER.may_load(deps.storage)? {
+            MINTER.save(deps.storage, &MinterData { minter, cap: None })?;
+        }
+
+        let balances = BALANCES
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        let mut holders = 0u64;
+        for (address, balance) in balances {
+            if balance.is_zero() {
+                BALANCES.remove(deps.storage, &address);
+            } else {
+                holders += 1;
+            }
+        }
+        HOLDER_COUNT.save(deps.storage, &holders)?;
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION))
+}
+
+// Validate a user supplied address, naming the message field in the error
+fn validate_addr(api: &dyn Api, field: &str, address: &str) -> Result<Addr, ContractError> {
+    api.addr_validate(address).map_err(|err| ContractError::InvalidAddress {
+        field: field.to_string(),
+        address: address.to_string(),
+        reason: err.to_string(),
+    })
+}
+
+fn parse_version(version: &str) -> StdResult<Version> {
+    version
+        .parse()
+        .map_err(|_| StdError::generic_err(format!("Invalid contract version: {}", version)))
+}
+
 // Execute contract methods (e.g., Transfer, Mint, Burn)
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: Cw20ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
         Cw20ExecuteMsg::Transfer { recipient, amount } => Ok(execute_transfer(deps, info, recipient, amount)?),
         Cw20ExecuteMsg::Mint { recipient, amount } => Ok(execute_mint(deps, info, recipient, amount)?),
+        Cw20ExecuteMsg::UpdateMinter { new_minter } => execute_update_minter(deps, info, new_minter),
         Cw20ExecuteMsg::Burn { amount } => Ok(execute_burn(deps, info, amount)?),
-        _ => Err(ContractError::Unauthorized {}),
+        Cw20ExecuteMsg::Send { contract, amount, msg } => execute_send(deps, info, contract, amount, msg),
+        Cw20ExecuteMsg::IncreaseAllowance { spender, amount, expires } => {
+            execute_increase_allowance(deps, env, info, spender, amount, expires)
+        }
+        Cw20ExecuteMsg::DecreaseAllowance { spender, amount, expires } => {
+            execute_decrease_allowance(deps, env, info, spender, amount, expires)
+        }
+        Cw20ExecuteMsg::TransferFrom { owner, recipient, amount } => {
+            execute_transfer_from(deps, env, info, owner, recipient, amount)
+        }
+        Cw20ExecuteMsg::SendFrom { owner, contract, amount, msg } => {
+            execute_send_from(deps, env, info, owner, contract, amount, msg)
+        }
+        Cw20ExecuteMsg::BurnFrom { owner, amount } => execute_burn_from(deps, env, info, owner, amount),
+        Cw20ExecuteMsg::UpdateMarketing { project, description, marketing } => {
+            execute_update_marketing(deps, info, project, description, marketing)
+        }
+        Cw20ExecuteMsg::UploadLogo(logo) => execute_upload_logo(deps, info, logo),
     }
 }
 
@@ -68,24 +231,40 @@ fn execute_transfer(
     recipient: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let sender_balance = BALANCES.load(deps.storage, &info.sender)?;
-    if sender_balance < amount {
-        return Err(ContractError::Std(StdError::generic_err("Insufficient funds")));
-    }
-
-    BALANCES.update(deps.storage, &info.sender, |balance| -> StdResult<_> {
-        let balance = balance.unwrap_or(Uint128::zero());
-        balance.checked_sub(amount).map_err(|_| StdError::generic_err("Overflow error"))
-    })?;
-    BALANCES.update(deps.storage, &Addr::unchecked(recipient.clone()), |balance| -> StdResult<_> {
-        let balance = balance.unwrap_or(Uint128::zero());
-        Ok(balance + amount)
-    })?;
+    let recipient = validate_addr(deps.api, "recipient", &recipient)?;
+    sub_balance(deps.storage, &info.sender, amount)?;
+    add_balance(deps.storage, &recipient, amount)?;
 
     Ok(Response::new()
         .add_attribute("method", "transfer")
         .add_attribute("from", info.sender.to_string())
-        .add_attribute("to", recipient)
+        .add_attribute("to", recipient.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn execute_send(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let contract = validate_addr(deps.api, "contract", &contract)?;
+    move_balance(deps.storage, &info.sender, &contract, amount)?;
+
+    // Notify the receiving contract so it can act on the tokens in the same transaction
+    let receive_msg = Cw20ReceiveMsg {
+        sender: info.sender.to_string(),
+        amount,
+        msg,
+    }
+    .into_cosmos_msg(contract.to_string())?;
+
+    Ok(Response::new()
+        .add_message(receive_msg)
+        .add_attribute("method", "send")
+        .add_attribute("from", info.sender.to_string())
+        .add_attribute("to", contract.to_string())
         .add_attribute("amount", amount.to_string()))
 }
 
@@ -95,43 +274,66 @@ fn execute_mint(
     recipient: String,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let minter = MINTER.load(deps.storage)?;
-    if info.sender != minter {
-        return Err(ContractError::Std(StdError::generic_err("Unauthorized: Only the minter can mint tokens")));
+    let minter = MINTER.may_load(deps.storage)?;
+    if minter.as_ref().map(|minter| &minter.minter) != Some(&info.sender) {
+        return Err(ContractError::Unauthorized {});
     }
 
-    let recipient_balance = BALANCES.load(deps.storage, &Addr::unchecked(recipient.clone())).unwrap_or(Uint128::zero());
-    BALANCES.save(deps.storage, &Addr::unchecked(recipient.clone()), &(recipient_balance + amount))?;
+    let recipient = validate_addr(deps.api, "recipient", &recipient)?;
+    add_balance(deps.storage, &recipient, amount)?;
 
     let mut token_info = TOKEN_INFO.load(deps.storage)?;
     token_info.total_supply += amount;
+    if let Some(cap) = minter.and_then(|minter| minter.cap).filter(|cap| token_info.total_supply > *cap) {
+        return Err(ContractError::CapExceeded {
+            total_supply: token_info.total_supply,
+            cap,
+        });
+    }
     TOKEN_INFO.save(deps.storage, &token_info)?;
 
     Ok(Response::new()
         .add_attribute("method", "mint")
-        .add_attribute("to", recipient)
+        .add_attribute("to", recipient.to_string())
         .add_attribute("amount", amount.to_string()))
 }
 
-fn execute_burn(
+// Hand the minter role to another address, or renounce it when none is given
+fn execute_update_minter(
     deps: DepsMut,
     info: MessageInfo,
-    amount: Uint128,
+    new_minter: Option<String>,
 ) -> Result<Response, ContractError> {
-    let sender_balance = BALANCES.load(deps.storage, &info.sender)?;
-    if sender_balance < amount {
-        return Err(ContractError::Std(StdError::generic_err("Insufficient funds")));
+    let mut minter = MINTER.may_load(deps.storage)?.ok_or(ContractError::Unauthorized {})?;
+    if info.sender != minter.minter {
+        return Err(ContractError::Unauthorized {});
     }
 
+    // The cap stays with the role, renouncing disables minting for good
+    match &new_minter {
+        Some(new_minter) => {
+            minter.minter = validate_addr(deps.api, "new_minter", new_minter)?;
+            MINTER.save(deps.storage, &minter)?;
+        }
+        None => MINTER.remove(deps.storage),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "update_minter")
+        .add_attribute("new_minter", new_minter.unwrap_or_else(|| "None".to_string())))
+}
+
+fn execute_burn(
+    deps: DepsMut,
+    info: MessageInfo,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
     // Update sender balance
-    BALANCES.update(deps.storage, &info.sender, |balance| -> StdResult<_> {
-        let balance = balance.unwrap_or(Uint128::zero());
-        balance.checked_sub(amount).map_err(|_| StdError::generic_err("Overflow error: cannot burn more than the balance"))
-    })?;
+    sub_balance(deps.storage, &info.sender, amount)?;
 
     let mut token_info = TOKEN_INFO.load(deps.storage)?;
     token_info.total_supply = token_info.total_supply.checked_sub(amount)
-        .map_err(|_| ContractError::Std(StdError::generic_err("Overflow error: total supply underflow")))?;
+        .map_err(StdError::from)?;
     TOKEN_INFO.save(deps.storage, &token_info)?;
 
     Ok(Response::new()
@@ -140,25 +342,370 @@ fn execute_burn(
         .add_attribute("amount", amount.to_string()))
 }
 
+fn execute_increase_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender = validate_addr(deps.api, "spender", &spender)?;
+    if spender == info.sender {
+        return Err(ContractError::OwnAllowance {});
+    }
+
+    let mut allowance = ALLOWANCES
+        .may_load(deps.storage, (&info.sender, &spender))?
+        .unwrap_or_default();
+    if let Some(expires) = expires {
+        if expires.is_expired(&env.block) {
+            return Err(ContractError::InvalidExpiration {});
+        }
+        allowance.expires = expires;
+    }
+    allowance.allowance += amount;
+    save_allowance(deps.storage, &info.sender, &spender, &allowance)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "increase_allowance")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("spender", spender.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn execute_decrease_allowance(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender = validate_addr(deps.api, "spender", &spender)?;
+    if spender == info.sender {
+        return Err(ContractError::OwnAllowance {});
+    }
+
+    let mut allowance = ALLOWANCES.load(deps.storage, (&info.sender, &spender))?;
+
+    // Decreasing below zero removes the allowance altogether
+    if amount >= allowance.allowance {
+        ALLOWANCES.remove(deps.storage, (&info.sender, &spender));
+        ALLOWANCES_SPENDER.remove(deps.storage, (&spender, &info.sender));
+    } else {
+        if let Some(expires) = expires {
+            if expires.is_expired(&env.block) {
+                return Err(ContractError::InvalidExpiration {});
+            }
+            allowance.expires = expires;
+        }
+        allowance.allowance -= amount;
+        save_allowance(deps.storage, &info.sender, &spender, &allowance)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "decrease_allowance")
+        .add_attribute("owner", info.sender.to_string())
+        .add_attribute("spender", spender.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn execute_transfer_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    recipient: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let owner = validate_addr(deps.api, "owner", &owner)?;
+    let recipient = validate_addr(deps.api, "recipient", &recipient)?;
+
+    deduct_allowance(deps.storage, &env, &owner, &info.sender, amount)?;
+    move_balance(deps.storage, &owner, &recipient, amount)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "transfer_from")
+        .add_attribute("from", owner.to_string())
+        .add_attribute("to", recipient.to_string())
+        .add_attribute("by", info.sender.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn execute_send_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    contract: String,
+    amount: Uint128,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let owner = validate_addr(deps.api, "owner", &owner)?;
+    let contract = validate_addr(deps.api, "contract", &contract)?;
+
+    deduct_allowance(deps.storage, &env, &owner, &info.sender, amount)?;
+    move_balance(deps.storage, &owner, &contract, amount)?;
+
+    // Notify the receiving contract, the spender is reported as the sender
+    let receive_msg = Cw20ReceiveMsg {
+        sender: info.sender.to_string(),
+        amount,
+        msg,
+    }
+    .into_cosmos_msg(contract.to_string())?;
+
+    Ok(Response::new()
+        .add_message(receive_msg)
+        .add_attribute("method", "send_from")
+        .add_attribute("from", owner.to_string())
+        .add_attribute("to", contract.to_string())
+        .add_attribute("by", info.sender.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+fn execute_burn_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    owner: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let owner = validate_addr(deps.api, "owner", &owner)?;
+
+    deduct_allowance(deps.storage, &env, &owner, &info.sender, amount)?;
+    sub_balance(deps.storage, &owner, amount)?;
+
+    let mut token_info = TOKEN_INFO.load(deps.storage)?;
+    token_info.total_supply = token_info.total_supply.checked_sub(amount)
+        .map_err(StdError::from)?;
+    TOKEN_INFO.save(deps.storage, &token_info)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "burn_from")
+        .add_attribute("from", owner.to_string())
+        .add_attribute("by", info.sender.to_string())
+        .add_attribute("amount", amount.to_string()))
+}
+
+// Update the marketing details, only callable by the marketing address.
+// An empty string clears a field.
+fn execute_update_marketing(
+    deps: DepsMut,
+    info: MessageInfo,
+    project: Option<String>,
+    description: Option<String>,
+    marketing: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut marketing_info = load_marketing_info(deps.as_ref(), &info.sender)?;
+
+    if let Some(project) = project {
+        marketing_info.project = Some(project).filter(|project| !project.is_empty());
+    }
+    if let Some(description) = description {
+        marketing_info.description = Some(description).filter(|description| !description.is_empty());
+    }
+    if let Some(marketing) = marketing {
+        marketing_info.marketing = if marketing.is_empty() {
+            None
+        } else {
+            Some(validate_addr(deps.api, "marketing", &marketing)?)
+        };
+    }
+    MARKETING_INFO.save(deps.storage, &marketing_info)?;
+
+    Ok(Response::new().add_attribute("method", "update_marketing"))
+}
+
+// Replace the logo, only callable by the marketing address
+fn execute_upload_logo(
+    deps: DepsMut,
+    info: MessageInfo,
+    logo: Logo,
+) -> Result<Response, ContractError> {
+    let mut marketing_info = load_marketing_info(deps.as_ref(), &info.sender)?;
+
+    verify_logo(&logo)?;
+    LOGO.save(deps.storage, &logo)?;
+    marketing_info.logo = Some(logo_info(&logo));
+    MARKETING_INFO.save(deps.storage, &marketing_info)?;
+
+    Ok(Response::new().add_attribute("method", "upload_logo"))
+}
+
+// Load the marketing details, ensuring the sender is the marketing address
+fn load_marketing_info(deps: Deps, sender: &Addr) -> Result<MarketingInfoResponse, ContractError> {
+    let marketing_info = MARKETING_INFO.may_load(deps.storage)?.ok_or(ContractError::Unauthorized {})?;
+    if marketing_info.marketing.as_ref() != Some(sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+    Ok(marketing_info)
+}
+
+// Check that a logo is an HTTP(S) URL, or a well-formed SVG or PNG within the size cap
+fn verify_logo(logo: &Logo) -> Result<(), ContractError> {
+    match logo {
+        Logo::Url(url) => {
+            if !url.starts_with("https://") && !url.starts_with("http://") {
+                return Err(ContractError::InvalidLogo {
+                    reason: "Logo URL must use HTTP or HTTPS".to_string(),
+                });
+            }
+        }
+        Logo::Embedded(EmbeddedLogo::Svg(data)) => {
+            verify_logo_size(data)?;
+            // An SVG is XML, so it must start with a tag once leading whitespace is skipped
+            if data.iter().find(|byte| !byte.is_ascii_whitespace()) != Some(&b'<') {
+                return Err(ContractError::InvalidLogo {
+                    reason: "Invalid SVG logo".to_string(),
+                });
+            }
+        }
+        Logo::Embedded(EmbeddedLogo::Png(data)) => {
+            verify_logo_size(data)?;
+            const PNG_HEADER: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+            if !data.starts_with(&PNG_HEADER) {
+                return Err(ContractError::InvalidLogo {
+                    reason: "Invalid PNG logo".to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+fn verify_logo_size(data: &Binary) -> Result<(), ContractError> {
+    if data.len() > LOGO_SIZE_CAP {
+        return Err(ContractError::LogoTooBig { max: LOGO_SIZE_CAP });
+    }
+    Ok(())
+}
+
+// How the logo is reported in the marketing info
+fn logo_info(logo: &Logo) -> LogoInfo {
+    match logo {
+        Logo::Url(url) => LogoInfo::Url(url.clone()),
+        Logo::Embedded(_) => LogoInfo::Embedded,
+    }
+}
+
+// Store an allowance under both the owner and the spender index
+fn save_allowance(
+    storage: &mut dyn Storage,
+    owner: &Addr,
+    spender: &Addr,
+    allowance: &AllowanceResponse,
+) -> StdResult<()> {
+    ALLOWANCES.save(storage, (owner, spender), allowance)?;
+    ALLOWANCES_SPENDER.save(storage, (spender, owner), allowance)
+}
+
+// Spend `amount` of the allowance granted by `owner` to `spender`
+fn deduct_allowance(
+    storage: &mut dyn Storage,
+    env: &Env,
+    owner: &Addr,
+    spender: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    let mut allowance = ALLOWANCES
+        .may_load(storage, (owner, spender))?
+        .unwrap_or_default();
+    if allowance.expires.is_expired(&env.block) {
+        return Err(ContractError::ExpiredAllowance {});
+    }
+    if allowance.allowance < amount {
+        return Err(ContractError::InsufficientAllowance {
+            needed: amount,
+            available: allowance.allowance,
+        });
+    }
+    allowance.allowance -= amount;
+    save_allowance(storage, owner, spender, &allowance)?;
+    Ok(())
+}
+
+// Move tokens between two balances
+fn move_balance(
+    storage: &mut dyn Storage,
+    from: &Addr,
+    to: &Addr,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    sub_balance(storage, from, amount)?;
+    Ok(add_balance(storage, to, amount)?)
+}
+
+// Credit an account, counting it as a new holder if it had no balance
+fn add_balance(storage: &mut dyn Storage, address: &Addr, amount: Uint128) -> StdResult<()> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let balance = BALANCES.may_load(storage, address)?.unwrap_or_default();
+    if balance.is_zero() {
+        HOLDER_COUNT.update(storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+    }
+    BALANCES.save(storage, address, &(balance + amount))
+}
+
+// Debit an account, removing it from the holders once its balance reaches zero
+fn sub_balance(storage: &mut dyn Storage, address: &Addr, amount: Uint128) -> Result<(), ContractError> {
+    if amount.is_zero() {
+        return Ok(());
+    }
+    let available = BALANCES.may_load(storage, address)?.unwrap_or_default();
+    if available < amount {
+        return Err(ContractError::InsufficientFunds {
+            needed: amount,
+            available,
+        });
+    }
+    let balance = available - amount;
+    if balance.is_zero() {
+        BALANCES.remove(storage, address);
+        HOLDER_COUNT.update(storage, |count| -> StdResult<_> { Ok(count.saturating_sub(1)) })?;
+    } else {
+        BALANCES.save(storage, address, &balance)?;
+    }
+    Ok(())
+}
+
 // Query contract state (e.g., total supply, balance)
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(
     deps: Deps,
     _env: Env,
-    msg: Cw20QueryMsg,
+    msg: QueryMsg,
 ) -> StdResult<Binary> {
     match msg {
-        Cw20QueryMsg::Balance { address } => to_json_binary(&query_balance(deps, address)?),
-        Cw20QueryMsg::TokenInfo {} => to_json_binary(&query_token_info(deps)?),
-        
-        // For unsupported queries, handle them here
-        _ => Err(StdError::generic_err("Unsupported query message")),
+        QueryMsg::Balance { address } => to_json_binary(&query_balance(deps, address)?),
+        QueryMsg::TokenInfo {} => to_json_binary(&query_token_info(deps)?),
+        QueryMsg::Minter {} => to_json_binary(&query_minter(deps)?),
+        QueryMsg::AllAccounts { start_after, limit } => {
+            to_json_binary(&query_all_accounts(deps, start_after, limit)?)
+        }
+        QueryMsg::HolderCount {} => to_json_binary(&HolderCountResponse {
+            count: HOLDER_COUNT.load(deps.storage)?,
+        }),
+        QueryMsg::MarketingInfo {} => to_json_binary(&query_marketing_info(deps)?),
+        QueryMsg::DownloadLogo {} => to_json_binary(&query_download_logo(deps)?),
+        QueryMsg::Allowance { owner, spender } => {
+            to_json_binary(&query_allowance(deps, owner, spender)?)
+        }
+        QueryMsg::AllAllowances { owner, start_after, limit } => {
+            to_json_binary(&query_all_allowances(deps, owner, start_after, limit)?)
+        }
+        QueryMsg::AllSpenderAllowances { spender, start_after, limit } => {
+            to_json_binary(&query_all_spender_allowances(deps, spender, start_after, limit)?)
+        }
     }
 }
 
 
 fn query_balance(deps: Deps, address: String) -> StdResult<BalanceResponse> {
-    let balance = BALANCES.load(deps.storage, &Addr::unchecked(address)).unwrap_or(Uint128::zero());
+    let address = validate_addr(deps.api, "address", &address)?;
+    let balance = BALANCES.may_load(deps.storage, &address)?.unwrap_or_default();
     Ok(BalanceResponse { balance })
 }
 
@@ -171,3 +718,107 @@ fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
         total_supply: token_info.total_supply,
     })
 }
+
+fn query_all_accounts(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllAccountsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(|address| validate_addr(deps.api, "start_after", &address)).transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let accounts = BALANCES
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|address| address.to_string()))
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AllAccountsResponse { accounts })
+}
+
+fn query_minter(deps: Deps) -> StdResult<Option<MinterResponse>> {
+    let minter = MINTER.may_load(deps.storage)?.map(|minter| MinterResponse {
+        minter: minter.minter.to_string(),
+        cap: minter.cap,
+    });
+    Ok(minter)
+}
+
+fn query_marketing_info(deps: Deps) -> StdResult<MarketingInfoResponse> {
+    Ok(MARKETING_INFO.may_load(deps.storage)?.unwrap_or_default())
+}
+
+fn query_download_logo(deps: Deps) -> StdResult<DownloadLogoResponse> {
+    match LOGO.may_load(deps.storage)? {
+        Some(Logo::Embedded(EmbeddedLogo::Svg(data))) => Ok(DownloadLogoResponse {
+            mime_type: "image/svg+xml".to_string(),
+            data,
+        }),
+        Some(Logo::Embedded(EmbeddedLogo::Png(data))) => Ok(DownloadLogoResponse {
+            mime_type: "image/png".to_string(),
+            data,
+        }),
+        _ => Err(StdError::not_found("logo")),
+    }
+}
+
+fn query_allowance(deps: Deps, owner: String, spender: String) -> StdResult<AllowanceResponse> {
+    let owner = validate_addr(deps.api, "owner", &owner)?;
+    let spender = validate_addr(deps.api, "spender", &spender)?;
+    let allowance = ALLOWANCES
+        .may_load(deps.storage, (&owner, &spender))?
+        .unwrap_or_default();
+    Ok(allowance)
+}
+
+fn query_all_allowances(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllAllowancesResponse> {
+    let owner = validate_addr(deps.api, "owner", &owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(|spender| validate_addr(deps.api, "start_after", &spender)).transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let allowances = ALLOWANCES
+        .prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(spender, allowance)| AllowanceInfo {
+                spender: spender.to_string(),
+                allowance: allowance.allowance,
+                expires: allowance.expires,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AllAllowancesResponse { allowances })
+}
+
+fn query_all_spender_allowances(
+    deps: Deps,
+    spender: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllSpenderAllowancesResponse> {
+    let spender = validate_addr(deps.api, "spender", &spender)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after.map(|owner| validate_addr(deps.api, "start_after", &owner)).transpose()?;
+    let start = start_after.as_ref().map(Bound::exclusive);
+
+    let allowances = ALLOWANCES_SPENDER
+        .prefix(&spender)
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(owner, allowance)| SpenderAllowanceInfo {
+                owner: owner.to_string(),
+                allowance: allowance.allowance,
+                expires: allowance.expires,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    Ok(AllSpenderAllowancesResponse { allowances })
+}
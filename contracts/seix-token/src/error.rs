@@ -1,13 +1,102 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Uint128};
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+// Errors returned by the contract. A failed transaction carries no response attributes,
+// so every error message starts with its stable code (see `ContractError::code`)
+// for clients to branch on.
+#[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
-    #[error("{0}")]
+    #[error("STD: {0}")]
     Std(#[from] StdError),
 
-    #[error("Unauthorized")]
+    #[error("UNAUTHORIZED: Unauthorized")]
     Unauthorized {},
-    // Add any other custom errors you like here.
-    // Look at https://docs.rs/thiserror/1.0.21/thiserror/ for details.
+
+    #[error("INVALID_ADDRESS: Invalid address in `{field}` ({address}): {reason}")]
+    InvalidAddress {
+        field: String,
+        address: String,
+        reason: String,
+    },
+
+    #[error("INVALID_NAME: Name must be between 3 and 50 bytes long")]
+    InvalidName {},
+
+    #[error("INVALID_SYMBOL: Symbol must be 3 to 12 characters matching [a-zA-Z-]")]
+    InvalidSymbol {},
+
+    #[error("INVALID_DECIMALS: Decimals must not exceed 18")]
+    InvalidDecimals {},
+
+    #[error("DUPLICATE_INITIAL_BALANCE: Duplicate initial balance for {address}")]
+    DuplicateInitialBalance { address: String },
+
+    #[error("INVALID_INITIAL_BALANCES: Initial balances add up to {distributed}, expected the initial supply of {total_supply}")]
+    InvalidInitialBalances {
+        distributed: Uint128,
+        total_supply: Uint128,
+    },
+
+    #[error("INSUFFICIENT_FUNDS: Insufficient funds: needed {needed}, available {available}")]
+    InsufficientFunds { needed: Uint128, available: Uint128 },
+
+    #[error("CAP_EXCEEDED: Total supply {total_supply} would exceed the cap of {cap}")]
+    CapExceeded { total_supply: Uint128, cap: Uint128 },
+
+    #[error("OWN_ALLOWANCE: Cannot set an allowance to own account")]
+    OwnAllowance {},
+
+    #[error("INVALID_EXPIRATION: Cannot set an expiration in the past")]
+    InvalidExpiration {},
+
+    #[error("EXPIRED_ALLOWANCE: Allowance is expired")]
+    ExpiredAllowance {},
+
+    #[error("INSUFFICIENT_ALLOWANCE: Insufficient allowance: needed {needed}, available {available}")]
+    InsufficientAllowance { needed: Uint128, available: Uint128 },
+
+    #[error("INVALID_LOGO: {reason}")]
+    InvalidLogo { reason: String },
+
+    #[error("LOGO_TOO_BIG: Logo is too big, the limit is {max} bytes")]
+    LogoTooBig { max: usize },
+
+    #[error("WRONG_CONTRACT: Cannot migrate from a different contract: {contract}")]
+    WrongContract { contract: String },
+
+    #[error("DOWNGRADE: Cannot migrate from newer version {from} to {to}")]
+    Downgrade { from: String, to: String },
+}
+
+impl ContractError {
+    // Stable code identifying the error, also the prefix of its message
+    pub fn code(&self) -> &'static str {
+        match self {
+            ContractError::Std(_) => "STD",
+            ContractError::Unauthorized {} => "UNAUTHORIZED",
+            ContractError::InvalidAddress { .. } => "INVALID_ADDRESS",
+            ContractError::InvalidName {} => "INVALID_NAME",
+            ContractError::InvalidSymbol {} => "INVALID_SYMBOL",
+            ContractError::InvalidDecimals {} => "INVALID_DECIMALS",
+            ContractError::DuplicateInitialBalance { .. } => "DUPLICATE_INITIAL_BALANCE",
+            ContractError::InvalidInitialBalances { .. } => "INVALID_INITIAL_BALANCES",
+            ContractError::InsufficientFunds { .. } => "INSUFFICIENT_FUNDS",
+            ContractError::CapExceeded { .. } => "CAP_EXCEEDED",
+            ContractError::OwnAllowance {} => "OWN_ALLOWANCE",
+            ContractError::InvalidExpiration {} => "INVALID_EXPIRATION",
+            ContractError::ExpiredAllowance {} => "EXPIRED_ALLOWANCE",
+            ContractError::InsufficientAllowance { .. } => "INSUFFICIENT_ALLOWANCE",
+            ContractError::InvalidLogo { .. } => "INVALID_LOGO",
+            ContractError::LogoTooBig { .. } => "LOGO_TOO_BIG",
+            ContractError::WrongContract { .. } => "WRONG_CONTRACT",
+            ContractError::Downgrade { .. } => "DOWNGRADE",
+        }
+    }
+}
+
+// Queries return `StdResult`, keep the message (which starts with the code) when they fail
+impl From<ContractError> for StdError {
+    fn from(err: ContractError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
 }
@@ -1,78 +1,794 @@
 #[cfg(test)]
 mod tests {
-    use crate::helpers::CwTemplateContract;
-    use crate::msg::InstantiateMsg;
+    use crate::helpers::Cw20Contract;
+    use crate::ContractError;
+    use crate::msg::{InstantiateMsg, MigrateMsg, QueryMsg};
+    use cosmwasm_schema::cw_serde;
     use cosmwasm_std::testing::MockApi;
-    use cosmwasm_std::{Addr, Coin, Empty, Uint128};
-    use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+    use cosmwasm_std::{Addr, Binary, Empty, Event, Response, StdError, Uint128};
+    use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg};
+    use cw_multi_test::error::AnyResult;
+    use cw_multi_test::{App, Contract, ContractWrapper, Executor};
 
     pub fn contract_template() -> Box<dyn Contract<Empty>> {
         let contract = ContractWrapper::new(
             crate::contract::execute,
             crate::contract::instantiate,
             crate::contract::query,
+        )
+        .with_migrate(crate::contract::migrate);
+        Box::new(contract)
+    }
+
+    #[cw_serde]
+    enum ReceiverExecuteMsg {
+        Receive(Cw20ReceiveMsg),
+    }
+
+    // A contract accepting CW20 tokens, reporting the hook it received as attributes
+    fn contract_receiver() -> Box<dyn Contract<Empty>> {
+        let contract = ContractWrapper::new(
+            |_, _, _, msg: ReceiverExecuteMsg| {
+                let ReceiverExecuteMsg::Receive(receive) = msg;
+                Ok::<_, StdError>(
+                    Response::new()
+                        .add_attribute("received_from", receive.sender)
+                        .add_attribute("received_amount", receive.amount),
+                )
+            },
+            |_, _, _, _: Empty| Ok::<_, StdError>(Response::new()),
+            |_, _, _: Empty| Err::<Binary, _>(StdError::generic_err("no queries")),
         );
         Box::new(contract)
     }
 
     const USER: &str = "USER";
     const ADMIN: &str = "ADMIN";
-    const NATIVE_DENOM: &str = "denom";
-
-    fn mock_app() -> App {
-        AppBuilder::new().build(|router, _, storage| {
-            router
-                .bank
-                .init_balance(
-                    storage,
-                    &MockApi::default().addr_make(USER),
-                    vec![Coin {
-                        denom: NATIVE_DENOM.to_string(),
-                        amount: Uint128::new(1),
-                    }],
-                )
-                .unwrap();
-        })
+
+    // The whole supply starts with the admin
+    fn instantiate_msg() -> InstantiateMsg {
+        InstantiateMsg {
+            name: "SeiX".to_string(),
+            symbol: "SEIX".to_string(),
+            decimals: 6,
+            initial_supply: 1_000_000,
+            initial_balances: vec![Cw20Coin {
+                address: MockApi::default().addr_make(ADMIN).to_string(),
+                amount: Uint128::new(1_000_000),
+            }],
+            mint: None,
+            marketing: None,
+        }
     }
 
-    fn proper_instantiate() -> (App, CwTemplateContract) {
-        let mut app = mock_app();
+    fn instantiate(msg: &InstantiateMsg) -> AnyResult<(App, Cw20Contract)> {
+        let mut app = App::default();
         let cw_template_id = app.store_code(contract_template());
 
-        let user = app.api().addr_make(USER);
-        assert_eq!(
-            app.wrap().query_balance(user, NATIVE_DENOM).unwrap().amount,
-            Uint128::new(1)
-        );
+        let admin = app.api().addr_make(ADMIN);
+        let cw_template_contract_addr = app.instantiate_contract(
+            cw_template_id,
+            admin.clone(),
+            msg,
+            &[],
+            "test",
+            Some(admin.to_string()),
+        )?;
+
+        let cw_template_contract = Cw20Contract(cw_template_contract_addr);
+
+        Ok((app, cw_template_contract))
+    }
+
+    fn proper_instantiate() -> (App, Cw20Contract) {
+        instantiate(&instantiate_msg()).unwrap()
+    }
+
+    fn balance(app: &App, cw_template_contract: &Cw20Contract, address: &Addr) -> Uint128 {
+        cw_template_contract
+            .balance::<_, _, Empty>(app, address)
+            .unwrap()
+            .balance
+    }
+
+    mod instantiate {
+        use super::*;
+
+        #[test]
+        fn distribute_initial_balances() {
+            let api = MockApi::default();
+            let (team, treasury) = (api.addr_make("team"), api.addr_make("treasury"));
+            let msg = InstantiateMsg {
+                initial_balances: vec![
+                    Cw20Coin {
+                        address: team.to_string(),
+                        amount: Uint128::new(400_000),
+                    },
+                    Cw20Coin {
+                        address: treasury.to_string(),
+                        amount: Uint128::new(600_000),
+                    },
+                ],
+                ..instantiate_msg()
+            };
+            let (app, cw_template_contract) = instantiate(&msg).unwrap();
+            assert_eq!(balance(&app, &cw_template_contract, &team), Uint128::new(400_000));
+            assert_eq!(balance(&app, &cw_template_contract, &treasury), Uint128::new(600_000));
+        }
+
+        #[test]
+        fn initial_balances_must_match_supply() {
+            let admin = MockApi::default().addr_make(ADMIN).to_string();
+            let coin = |amount| Cw20Coin {
+                address: admin.clone(),
+                amount: Uint128::new(amount),
+            };
 
-        let msg = InstantiateMsg { count: 1i32 };
-        let cw_template_contract_addr = app
-            .instantiate_contract(
-                cw_template_id,
-                Addr::unchecked(ADMIN),
-                &msg,
-                &[],
-                "test",
-                None,
-            )
-            .unwrap();
-
-        let cw_template_contract = CwTemplateContract(cw_template_contract_addr);
-
-        (app, cw_template_contract)
+            // Short of the supply
+            let msg = InstantiateMsg {
+                initial_balances: vec![coin(999_999)],
+                ..instantiate_msg()
+            };
+            assert!(instantiate(&msg).is_err());
+
+            // The same address listed twice
+            let msg = InstantiateMsg {
+                initial_balances: vec![coin(500_000), coin(500_000)],
+                ..instantiate_msg()
+            };
+            assert!(instantiate(&msg).is_err());
+        }
+
+        #[test]
+        fn token_info_is_validated() {
+            let invalid = [
+                InstantiateMsg { name: "SX".to_string(), ..instantiate_msg() },
+                InstantiateMsg { name: "S".repeat(51), ..instantiate_msg() },
+                InstantiateMsg { symbol: "SX".to_string(), ..instantiate_msg() },
+                InstantiateMsg { symbol: "SEIX1".to_string(), ..instantiate_msg() },
+                InstantiateMsg { symbol: "SEIXSEIXSEIXS".to_string(), ..instantiate_msg() },
+                InstantiateMsg { decimals: 19, ..instantiate_msg() },
+            ];
+            for msg in invalid {
+                assert!(instantiate(&msg).is_err());
+            }
+
+            let msg = InstantiateMsg { symbol: "S-EIX".to_string(), decimals: 18, ..instantiate_msg() };
+            instantiate(&msg).map(|_| ()).unwrap();
+        }
     }
 
-    mod count {
+    mod transfer {
         use super::*;
-        use crate::msg::ExecuteMsg;
 
         #[test]
-        fn count() {
+        fn transfer() {
             let (mut app, cw_template_contract) = proper_instantiate();
 
-            let msg = ExecuteMsg::Increment {};
+            let admin = app.api().addr_make(ADMIN);
+            let user = app.api().addr_make(USER);
+            let msg = Cw20ExecuteMsg::Transfer {
+                recipient: user.to_string(),
+                amount: Uint128::new(100),
+            };
             let cosmos_msg = cw_template_contract.call(msg).unwrap();
-            app.execute(Addr::unchecked(USER), cosmos_msg).unwrap();
+            app.execute(admin.clone(), cosmos_msg).unwrap();
+
+            assert_eq!(
+                balance(&app, &cw_template_contract, &user),
+                Uint128::new(100)
+            );
+            assert_eq!(
+                balance(&app, &cw_template_contract, &admin),
+                Uint128::new(999_900)
+            );
+        }
+
+        #[test]
+        fn transfer_rejects_invalid_recipient() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+
+            // A mistyped recipient must not swallow the tokens
+            let admin = app.api().addr_make(ADMIN);
+            let msg = Cw20ExecuteMsg::Transfer {
+                recipient: "not an address".to_string(),
+                amount: Uint128::new(100),
+            };
+            let err = app
+                .execute(admin.clone(), cw_template_contract.call(msg).unwrap())
+                .unwrap_err();
+            assert!(err.root_cause().to_string().contains("`recipient`"));
+            assert_eq!(balance(&app, &cw_template_contract, &admin), Uint128::new(1_000_000));
+        }
+
+        #[test]
+        fn transfer_more_than_balance() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+
+            let admin = app.api().addr_make(ADMIN);
+            let msg = Cw20ExecuteMsg::Transfer {
+                recipient: app.api().addr_make(USER).to_string(),
+                amount: Uint128::new(1_000_001),
+            };
+            let err = app
+                .execute(admin, cw_template_contract.call(msg).unwrap())
+                .unwrap_err();
+            let err = err.downcast::<ContractError>().unwrap();
+            assert_eq!(
+                err,
+                ContractError::InsufficientFunds {
+                    needed: Uint128::new(1_000_001),
+                    available: Uint128::new(1_000_000),
+                }
+            );
+            assert_eq!(err.code(), "INSUFFICIENT_FUNDS");
+            assert!(err.to_string().starts_with("INSUFFICIENT_FUNDS: "));
+        }
+    }
+
+    mod send {
+        use super::*;
+
+        #[test]
+        fn send_calls_receive_hook() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+            let receiver_id = app.store_code(contract_receiver());
+            let receiver = app
+                .instantiate_contract(receiver_id, admin.clone(), &Empty {}, &[], "receiver", None)
+                .unwrap();
+
+            let msg = Cw20ExecuteMsg::Send {
+                contract: receiver.to_string(),
+                amount: Uint128::new(250),
+                msg: Binary::default(),
+            };
+            let res = app.execute(admin.clone(), cw_template_contract.call(msg).unwrap()).unwrap();
+            assert!(res.has_event(
+                &Event::new("wasm")
+                    .add_attribute("received_from", admin.to_string())
+                    .add_attribute("received_amount", "250")
+            ));
+            assert_eq!(balance(&app, &cw_template_contract, &receiver), Uint128::new(250));
+            assert_eq!(balance(&app, &cw_template_contract, &admin), Uint128::new(999_750));
+        }
+
+        #[test]
+        fn failed_hook_reverts_send() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+
+            // The token contract itself has no Receive hook, so the whole send is rolled back
+            let msg = Cw20ExecuteMsg::Send {
+                contract: cw_template_contract.addr().to_string(),
+                amount: Uint128::new(250),
+                msg: Binary::default(),
+            };
+            app.execute(admin.clone(), cw_template_contract.call(msg).unwrap()).unwrap_err();
+            assert_eq!(balance(&app, &cw_template_contract, &admin), Uint128::new(1_000_000));
+        }
+
+        #[test]
+        fn send_requires_funds() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let user = app.api().addr_make(USER);
+            let msg = Cw20ExecuteMsg::Send {
+                contract: cw_template_contract.addr().to_string(),
+                amount: Uint128::new(1),
+                msg: Binary::default(),
+            };
+            app.execute(user, cw_template_contract.call(msg).unwrap()).unwrap_err();
+        }
+    }
+
+    mod allowance {
+        use super::*;
+        use cw20::{
+            AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceResponse, Expiration,
+        };
+
+        const SPENDER: &str = "SPENDER";
+
+        fn allowance(app: &App, cw_template_contract: &Cw20Contract, owner: &Addr, spender: &Addr) -> AllowanceResponse {
+            app.wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::Allowance {
+                        owner: owner.to_string(),
+                        spender: spender.to_string(),
+                    },
+                )
+                .unwrap()
+        }
+
+        fn increase_allowance(app: &mut App, cw_template_contract: &Cw20Contract, amount: u128, expires: Option<Expiration>) {
+            let admin = app.api().addr_make(ADMIN);
+            let spender = app.api().addr_make(SPENDER);
+            let msg = Cw20ExecuteMsg::IncreaseAllowance {
+                spender: spender.to_string(),
+                amount: Uint128::new(amount),
+                expires,
+            };
+            app.execute(admin, cw_template_contract.call(msg).unwrap()).unwrap();
+        }
+
+        #[test]
+        fn increase_and_decrease_allowance() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+            let spender = app.api().addr_make(SPENDER);
+
+            increase_allowance(&mut app, &cw_template_contract, 300, None);
+            increase_allowance(&mut app, &cw_template_contract, 200, Some(Expiration::AtHeight(1_000_000)));
+            let current = allowance(&app, &cw_template_contract, &admin, &spender);
+            assert_eq!(current.allowance, Uint128::new(500));
+            assert_eq!(current.expires, Expiration::AtHeight(1_000_000));
+
+            let msg = Cw20ExecuteMsg::DecreaseAllowance {
+                spender: spender.to_string(),
+                amount: Uint128::new(100),
+                expires: None,
+            };
+            app.execute(admin.clone(), cw_template_contract.call(msg).unwrap()).unwrap();
+            assert_eq!(allowance(&app, &cw_template_contract, &admin, &spender).allowance, Uint128::new(400));
+
+            // Decreasing by more than what is left removes the allowance
+            let msg = Cw20ExecuteMsg::DecreaseAllowance {
+                spender: spender.to_string(),
+                amount: Uint128::new(1_000),
+                expires: None,
+            };
+            app.execute(admin.clone(), cw_template_contract.call(msg).unwrap()).unwrap();
+            assert_eq!(allowance(&app, &cw_template_contract, &admin, &spender), AllowanceResponse::default());
+        }
+
+        #[test]
+        fn transfer_from_spends_allowance() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+            let spender = app.api().addr_make(SPENDER);
+            let user = app.api().addr_make(USER);
+            increase_allowance(&mut app, &cw_template_contract, 300, None);
+
+            let msg = Cw20ExecuteMsg::TransferFrom {
+                owner: admin.to_string(),
+                recipient: user.to_string(),
+                amount: Uint128::new(200),
+            };
+            app.execute(spender.clone(), cw_template_contract.call(msg.clone()).unwrap()).unwrap();
+            assert_eq!(balance(&app, &cw_template_contract, &user), Uint128::new(200));
+            assert_eq!(balance(&app, &cw_template_contract, &admin), Uint128::new(999_800));
+            assert_eq!(allowance(&app, &cw_template_contract, &admin, &spender).allowance, Uint128::new(100));
+
+            // The remaining allowance does not cover another transfer
+            app.execute(spender, cw_template_contract.call(msg).unwrap()).unwrap_err();
+        }
+
+        #[test]
+        fn expired_allowance_cannot_be_spent() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+            let spender = app.api().addr_make(SPENDER);
+            let height = app.block_info().height;
+            increase_allowance(&mut app, &cw_template_contract, 300, Some(Expiration::AtHeight(height + 1)));
+
+            app.update_block(|block| block.height += 1);
+            let msg = Cw20ExecuteMsg::BurnFrom {
+                owner: admin.to_string(),
+                amount: Uint128::new(100),
+            };
+            app.execute(spender, cw_template_contract.call(msg).unwrap()).unwrap_err();
+        }
+
+        #[test]
+        fn burn_from_reduces_supply() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+            let spender = app.api().addr_make(SPENDER);
+            increase_allowance(&mut app, &cw_template_contract, 300, None);
+
+            let msg = Cw20ExecuteMsg::BurnFrom {
+                owner: admin.to_string(),
+                amount: Uint128::new(300),
+            };
+            app.execute(spender, cw_template_contract.call(msg).unwrap()).unwrap();
+            assert_eq!(balance(&app, &cw_template_contract, &admin), Uint128::new(999_700));
+            let info = cw_template_contract.token_info::<_, Empty>(&app).unwrap();
+            assert_eq!(info.total_supply, Uint128::new(999_700));
+        }
+
+        #[test]
+        fn send_from_calls_receive_hook() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+            let spender = app.api().addr_make(SPENDER);
+            increase_allowance(&mut app, &cw_template_contract, 300, None);
+
+            let receiver_id = app.store_code(contract_receiver());
+            let receiver = app
+                .instantiate_contract(receiver_id, admin.clone(), &Empty {}, &[], "receiver", None)
+                .unwrap();
+
+            let msg = Cw20ExecuteMsg::SendFrom {
+                owner: admin.to_string(),
+                contract: receiver.to_string(),
+                amount: Uint128::new(300),
+                msg: Binary::default(),
+            };
+            let res = app.execute(spender.clone(), cw_template_contract.call(msg).unwrap()).unwrap();
+            assert!(res.has_event(
+                &Event::new("wasm").add_attribute("received_from", spender.to_string())
+            ));
+            assert_eq!(balance(&app, &cw_template_contract, &receiver), Uint128::new(300));
+        }
+
+        #[test]
+        fn list_allowances() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+            let spender = app.api().addr_make(SPENDER);
+            increase_allowance(&mut app, &cw_template_contract, 300, None);
+
+            let by_owner: AllAllowancesResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::AllAllowances {
+                        owner: admin.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(by_owner.allowances.len(), 1);
+            assert_eq!(by_owner.allowances[0].spender, spender.to_string());
+
+            let by_spender: AllSpenderAllowancesResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::AllSpenderAllowances {
+                        spender: spender.to_string(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap();
+            assert_eq!(by_spender.allowances.len(), 1);
+            assert_eq!(by_spender.allowances[0].owner, admin.to_string());
+            assert_eq!(by_spender.allowances[0].allowance, Uint128::new(300));
+        }
+    }
+
+    mod minter {
+        use super::*;
+        use cw20::MinterResponse;
+
+        const MINTER: &str = "MINTER";
+
+        // Instantiate SeiX with a minter allowed to mint up to the cap
+        fn instantiate_with_minter(cap: Option<u128>) -> AnyResult<(App, Cw20Contract)> {
+            let msg = InstantiateMsg {
+                mint: Some(MinterResponse {
+                    minter: MockApi::default().addr_make(MINTER).to_string(),
+                    cap: cap.map(Uint128::new),
+                }),
+                ..instantiate_msg()
+            };
+            instantiate(&msg)
+        }
+
+        fn minter(app: &App, cw_template_contract: &Cw20Contract) -> Option<MinterResponse> {
+            app.wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::Minter {})
+                .unwrap()
+        }
+
+        fn mint(app: &mut App, cw_template_contract: &Cw20Contract, sender: &Addr, amount: u128) -> AnyResult<()> {
+            let user = app.api().addr_make(USER);
+            let msg = Cw20ExecuteMsg::Mint {
+                recipient: user.to_string(),
+                amount: Uint128::new(amount),
+            };
+            app.execute(sender.clone(), cw_template_contract.call(msg).unwrap())
+                .map(|_| ())
+        }
+
+        #[test]
+        fn mint_up_to_cap() {
+            let (mut app, cw_template_contract) = instantiate_with_minter(Some(1_500_000)).unwrap();
+            let minter_addr = app.api().addr_make(MINTER);
+            assert_eq!(
+                minter(&app, &cw_template_contract),
+                Some(MinterResponse {
+                    minter: minter_addr.to_string(),
+                    cap: Some(Uint128::new(1_500_000)),
+                })
+            );
+
+            // Only the minter can mint
+            let admin = app.api().addr_make(ADMIN);
+            mint(&mut app, &cw_template_contract, &admin, 100).unwrap_err();
+
+            mint(&mut app, &cw_template_contract, &minter_addr, 500_000).unwrap();
+            mint(&mut app, &cw_template_contract, &minter_addr, 1).unwrap_err();
+            let info = cw_template_contract.token_info::<_, Empty>(&app).unwrap();
+            assert_eq!(info.total_supply, Uint128::new(1_500_000));
+        }
+
+        #[test]
+        fn initial_supply_cannot_exceed_cap() {
+            assert!(instantiate_with_minter(Some(999_999)).is_err());
+        }
+
+        #[test]
+        fn rotate_and_renounce_minter() {
+            let (mut app, cw_template_contract) = instantiate_with_minter(None).unwrap();
+            let minter_addr = app.api().addr_make(MINTER);
+            let admin = app.api().addr_make(ADMIN);
+
+            // Only the current minter can hand over the role
+            let msg = Cw20ExecuteMsg::UpdateMinter {
+                new_minter: Some(admin.to_string()),
+            };
+            app.execute(admin.clone(), cw_template_contract.call(msg.clone()).unwrap()).unwrap_err();
+            app.execute(minter_addr.clone(), cw_template_contract.call(msg).unwrap()).unwrap();
+            mint(&mut app, &cw_template_contract, &minter_addr, 100).unwrap_err();
+            mint(&mut app, &cw_template_contract, &admin, 100).unwrap();
+
+            // Renouncing disables minting
+            let msg = Cw20ExecuteMsg::UpdateMinter { new_minter: None };
+            app.execute(admin.clone(), cw_template_contract.call(msg).unwrap()).unwrap();
+            assert_eq!(minter(&app, &cw_template_contract), None);
+            mint(&mut app, &cw_template_contract, &admin, 100).unwrap_err();
+        }
+    }
+
+    mod marketing {
+        use super::*;
+        use crate::msg::InstantiateMarketingInfo;
+        use cw20::{DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo, MarketingInfoResponse};
+
+        const MARKETING: &str = "MARKETING";
+        const SVG_LOGO: &[u8] = b"<svg xmlns=\"http://www.w3.org/2000/svg\"></svg>";
+
+        fn instantiate_with_marketing() -> (App, Cw20Contract) {
+            let msg = InstantiateMsg {
+                marketing: Some(InstantiateMarketingInfo {
+                    project: Some("https://seibond.io".to_string()),
+                    description: Some("SeiBond platform token".to_string()),
+                    marketing: Some(MockApi::default().addr_make(MARKETING).to_string()),
+                    logo: Some(Logo::Embedded(EmbeddedLogo::Svg(Binary::from(SVG_LOGO)))),
+                }),
+                ..instantiate_msg()
+            };
+            instantiate(&msg).unwrap()
+        }
+
+        fn marketing_info(app: &App, cw_template_contract: &Cw20Contract) -> MarketingInfoResponse {
+            app.wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::MarketingInfo {})
+                .unwrap()
+        }
+
+        #[test]
+        fn query_marketing_info_and_logo() {
+            let (app, cw_template_contract) = instantiate_with_marketing();
+            let marketing = app.api().addr_make(MARKETING);
+
+            let info = marketing_info(&app, &cw_template_contract);
+            assert_eq!(info.project, Some("https://seibond.io".to_string()));
+            assert_eq!(info.marketing, Some(marketing));
+            assert_eq!(info.logo, Some(LogoInfo::Embedded));
+
+            let logo: DownloadLogoResponse = app
+                .wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::DownloadLogo {})
+                .unwrap();
+            assert_eq!(logo.mime_type, "image/svg+xml");
+            assert_eq!(logo.data, Binary::from(SVG_LOGO));
+        }
+
+        #[test]
+        fn update_marketing_requires_marketing_address() {
+            let (mut app, cw_template_contract) = instantiate_with_marketing();
+            let admin = app.api().addr_make(ADMIN);
+            let marketing = app.api().addr_make(MARKETING);
+
+            let msg = Cw20ExecuteMsg::UpdateMarketing {
+                project: None,
+                description: Some(String::new()),
+                marketing: Some(admin.to_string()),
+            };
+            app.execute(admin.clone(), cw_template_contract.call(msg.clone()).unwrap()).unwrap_err();
+            app.execute(marketing, cw_template_contract.call(msg).unwrap()).unwrap();
+
+            // An empty description clears it, the role moves to the new address
+            let info = marketing_info(&app, &cw_template_contract);
+            assert_eq!(info.project, Some("https://seibond.io".to_string()));
+            assert_eq!(info.description, None);
+            assert_eq!(info.marketing, Some(admin));
+        }
+
+        #[test]
+        fn upload_logo_is_validated() {
+            let (mut app, cw_template_contract) = instantiate_with_marketing();
+            let marketing = app.api().addr_make(MARKETING);
+
+            let invalid = [
+                Logo::Url("ftp://seibond.io/logo.png".to_string()),
+                Logo::Embedded(EmbeddedLogo::Png(Binary::from(SVG_LOGO))),
+                Logo::Embedded(EmbeddedLogo::Svg(Binary::from(b"not an image".as_slice()))),
+                Logo::Embedded(EmbeddedLogo::Svg(Binary::new(vec![b'<'; 5 * 1024 + 1]))),
+            ];
+            for logo in invalid {
+                let msg = Cw20ExecuteMsg::UploadLogo(logo);
+                app.execute(marketing.clone(), cw_template_contract.call(msg).unwrap()).unwrap_err();
+            }
+
+            let url = "https://seibond.io/logo.png".to_string();
+            let msg = Cw20ExecuteMsg::UploadLogo(Logo::Url(url.clone()));
+            app.execute(marketing, cw_template_contract.call(msg).unwrap()).unwrap();
+            assert_eq!(marketing_info(&app, &cw_template_contract).logo, Some(LogoInfo::Url(url)));
+
+            // Only embedded logos can be downloaded
+            app.wrap()
+                .query_wasm_smart::<DownloadLogoResponse>(cw_template_contract.addr(), &QueryMsg::DownloadLogo {})
+                .unwrap_err();
+        }
+    }
+
+    mod accounts {
+        use super::*;
+        use crate::msg::HolderCountResponse;
+        use cw20::AllAccountsResponse;
+
+        fn transfer(app: &mut App, cw_template_contract: &Cw20Contract, from: &Addr, to: &Addr, amount: u128) {
+            let msg = Cw20ExecuteMsg::Transfer {
+                recipient: to.to_string(),
+                amount: Uint128::new(amount),
+            };
+            app.execute(from.clone(), cw_template_contract.call(msg).unwrap()).unwrap();
+        }
+
+        fn all_accounts(app: &App, cw_template_contract: &Cw20Contract, start_after: Option<String>, limit: u32) -> Vec<String> {
+            let response: AllAccountsResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    cw_template_contract.addr(),
+                    &QueryMsg::AllAccounts {
+                        start_after,
+                        limit: Some(limit),
+                    },
+                )
+                .unwrap();
+            response.accounts
+        }
+
+        fn holder_count(app: &App, cw_template_contract: &Cw20Contract) -> u64 {
+            let response: HolderCountResponse = app
+                .wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::HolderCount {})
+                .unwrap();
+            response.count
+        }
+
+        #[test]
+        fn list_accounts_paginated() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+            let mut holders: Vec<String> = vec![admin.to_string()];
+            for name in ["alice", "bob", "carol"] {
+                let holder = app.api().addr_make(name);
+                transfer(&mut app, &cw_template_contract, &admin, &holder, 10);
+                holders.push(holder.to_string());
+            }
+            holders.sort();
+            assert_eq!(holder_count(&app, &cw_template_contract), 4);
+
+            let first = all_accounts(&app, &cw_template_contract, None, 2);
+            assert_eq!(first, holders[..2]);
+            let rest = all_accounts(&app, &cw_template_contract, first.last().cloned(), 2);
+            assert_eq!(rest, holders[2..]);
+        }
+
+        #[test]
+        fn emptied_accounts_are_no_longer_holders() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+            let user = app.api().addr_make(USER);
+
+            transfer(&mut app, &cw_template_contract, &admin, &user, 100);
+            assert_eq!(holder_count(&app, &cw_template_contract), 2);
+
+            // Burning the whole balance drops the user from the holders
+            let msg = Cw20ExecuteMsg::Burn { amount: Uint128::new(100) };
+            app.execute(user.clone(), cw_template_contract.call(msg).unwrap()).unwrap();
+            assert_eq!(holder_count(&app, &cw_template_contract), 1);
+            assert_eq!(all_accounts(&app, &cw_template_contract, None, 10), vec![admin.to_string()]);
+        }
+    }
+
+    mod migration {
+        use super::*;
+        use crate::state::{BALANCES, HOLDER_COUNT, LEGACY_MINTER};
+        use cw20::MinterResponse;
+
+        fn migrate(app: &mut App, cw_template_contract: &Cw20Contract) -> AnyResult<()> {
+            let code_id = app
+                .wrap()
+                .query_wasm_contract_info(cw_template_contract.addr())
+                .unwrap()
+                .code_id;
+            let admin = app.api().addr_make(ADMIN);
+            app.migrate_contract(admin, cw_template_contract.addr(), &MigrateMsg {}, code_id)
+                .map(|_| ())
+        }
+
+        #[test]
+        fn migrate_same_version() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            migrate(&mut app, &cw_template_contract).unwrap();
+
+            let version =
+                cw2::query_contract_info(&app.wrap(), cw_template_contract.addr()).unwrap();
+            assert_eq!(version.version, env!("CARGO_PKG_VERSION"));
+        }
+
+        #[test]
+        fn migrate_legacy_minter() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let admin = app.api().addr_make(ADMIN);
+            {
+                let mut storage = app.contract_storage_mut(&cw_template_contract.addr());
+                LEGACY_MINTER.save(storage.as_mut(), &admin).unwrap();
+                cw2::set_contract_version(storage.as_mut(), "crates.io:seix-token", "0.1.0")
+                    .unwrap();
+            }
+            migrate(&mut app, &cw_template_contract).unwrap();
+
+            let minter: Option<MinterResponse> = app
+                .wrap()
+                .query_wasm_smart(cw_template_contract.addr(), &QueryMsg::Minter {})
+                .unwrap();
+            assert_eq!(
+                minter,
+                Some(MinterResponse {
+                    minter: admin.to_string(),
+                    cap: None,
+                })
+            );
+        }
+
+        #[test]
+        fn migrate_counts_holders() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            let user = app.api().addr_make(USER);
+            {
+                // Version 0.1.0 kept empty balances and did not count holders
+                let mut storage = app.contract_storage_mut(&cw_template_contract.addr());
+                BALANCES.save(storage.as_mut(), &user, &Uint128::zero()).unwrap();
+                HOLDER_COUNT.remove(storage.as_mut());
+                cw2::set_contract_version(storage.as_mut(), "crates.io:seix-token", "0.1.0")
+                    .unwrap();
+            }
+            migrate(&mut app, &cw_template_contract).unwrap();
+
+            let storage = app.contract_storage(&cw_template_contract.addr());
+            assert!(!BALANCES.has(storage.as_ref(), &user));
+            assert_eq!(HOLDER_COUNT.load(storage.as_ref()).unwrap(), 1);
+        }
+
+        #[test]
+        fn refuse_downgrade() {
+            let (mut app, cw_template_contract) = proper_instantiate();
+            {
+                let mut storage = app.contract_storage_mut(&cw_template_contract.addr());
+                cw2::set_contract_version(storage.as_mut(), "crates.io:seix-token", "9.0.0")
+                    .unwrap();
+            }
+            let err = migrate(&mut app, &cw_template_contract).unwrap_err();
+            assert!(err.root_cause().to_string().contains("newer version"));
         }
     }
 }
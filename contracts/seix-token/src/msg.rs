@@ -1,4 +1,9 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
+use cw20::{
+    AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceResponse, BalanceResponse,
+    AllAccountsResponse, Cw20Coin, DownloadLogoResponse, Logo, MarketingInfoResponse,
+    MinterResponse, TokenInfoResponse,
+};
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -6,16 +11,23 @@ pub struct InstantiateMsg {
     pub symbol: String,
     pub decimals: u8,
     pub initial_supply: u128,
-    pub minter: Option<String>,  // Optional minter address
+    pub initial_balances: Vec<Cw20Coin>,  // Genesis distribution, must add up to the initial supply
+    pub mint: Option<MinterResponse>,  // Optional minter address and supply cap
+    pub marketing: Option<InstantiateMarketingInfo>,  // Optional marketing details and logo
 }
 
 #[cw_serde]
-pub enum ExecuteMsg {
-    Transfer { recipient: String, amount: u128 },
-    Burn { amount: u128 },
-    Mint { recipient: String, amount: u128 },
+pub struct InstantiateMarketingInfo {
+    pub project: Option<String>,      // URL of the project behind the token
+    pub description: Option<String>,  // Longer description of the token
+    pub marketing: Option<String>,    // Address allowed to update the marketing info
+    pub logo: Option<Logo>,           // Logo URL or embedded SVG/PNG (at most 5KB)
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
+// The CW20 queries, plus AllSpenderAllowances which `cw20::Cw20QueryMsg` does not define
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -26,19 +38,53 @@ pub enum QueryMsg {
     // Get token info
     #[returns(TokenInfoResponse)]
     TokenInfo {},
-}
 
-// Response for Balance query
-#[cw_serde]
-pub struct BalanceResponse {
-    pub balance: u128,
+    // Get the minter and the supply cap, if minting is enabled
+    #[returns(Option<MinterResponse>)]
+    Minter {},
+
+    // Get the marketing details and where to find the logo
+    #[returns(MarketingInfoResponse)]
+    MarketingInfo {},
+
+    // Download the logo, if it is embedded on chain
+    #[returns(DownloadLogoResponse)]
+    DownloadLogo {},
+
+    // List the accounts holding a balance, ordered by address
+    #[returns(AllAccountsResponse)]
+    AllAccounts {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // Get the number of accounts holding a balance
+    #[returns(HolderCountResponse)]
+    HolderCount {},
+
+    // Get how much the spender can still move on behalf of the owner
+    #[returns(AllowanceResponse)]
+    Allowance { owner: String, spender: String },
+
+    // List the allowances granted by an owner
+    #[returns(AllAllowancesResponse)]
+    AllAllowances {
+        owner: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // List the allowances granted to a spender
+    #[returns(AllSpenderAllowancesResponse)]
+    AllSpenderAllowances {
+        spender: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
-// Response for TokenInfo query
+// Response for HolderCount query
 #[cw_serde]
-pub struct TokenInfoResponse {
-    pub name: String,
-    pub symbol: String,
-    pub decimals: u8,
-    pub total_supply: u128,
+pub struct HolderCountResponse {
+    pub count: u64,
 }
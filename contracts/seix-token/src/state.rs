@@ -1,4 +1,5 @@
 use cosmwasm_std::{Addr, Uint128};
+use cw20::{AllowanceResponse, Logo, MarketingInfoResponse};
 use cw_storage_plus::{Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -6,11 +7,38 @@ use serde::{Deserialize, Serialize};
 // Track the balances of all users
 pub const BALANCES: Map<&Addr, Uint128> = Map::new("balances");
 
+// Number of accounts holding a positive balance, accounts are removed from
+// `BALANCES` once their balance drops to zero
+pub const HOLDER_COUNT: Item<u64> = Item::new("holder_count");
+
+// Track the allowances granted by an owner to a spender, keyed by (owner, spender)
+pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowances");
+
+// The same allowances keyed by (spender, owner), to list them per spender
+pub const ALLOWANCES_SPENDER: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowances_spender");
+
 // Track the total supply of tokens
 pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
 
-// Optional minter (address that can mint more tokens)
-pub const MINTER: Item<Addr> = Item::new("minter");
+// Optional minter (address that can mint more tokens, up to the cap)
+pub const MINTER: Item<MinterData> = Item::new("minter");
+
+// The minter as stored before version 0.2.0, a bare address without a cap.
+// Only read by `migrate` to move it over to `MINTER`.
+pub const LEGACY_MINTER: Item<Addr> = Item::new("minter");
+
+// Marketing details shown by explorers and wallets
+pub const MARKETING_INFO: Item<MarketingInfoResponse> = Item::new("marketing_info");
+
+// The token logo, either a URL or an image embedded on chain
+pub const LOGO: Item<Logo> = Item::new("logo");
+
+// Define a struct for the minter and the supply cap it mints up to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MinterData {
+    pub minter: Addr,
+    pub cap: Option<Uint128>,    // Maximum total supply, unlimited if not set
+}
 
 // Define a struct for token information
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]